@@ -0,0 +1,140 @@
+// Turns the example files into tests. Every examples/dayNN/<name>.txt has a
+// <name>.expected next to it with one answer per line:
+//
+//     # comments and blank lines are skipped
+//     part_one: 13
+//     part_one(10): 40
+//
+// The key is the solver to call, anything in parentheses is passed after the
+// input. This writes $OUT_DIR/examples/dayNN.rs with a test per example per
+// line, which the day pulls into its tests module with example_tests!()
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct Answer {
+    solver: String,
+    extra_args: Option<String>,
+    expected: String,
+}
+
+fn parse_expected(path: &Path) -> Vec<Answer> {
+    let contents = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Couldn't read {}: {}", path.display(), e));
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            let (key, expected) = l.split_once(':').unwrap_or_else(|| {
+                panic!(
+                    "{}: expected 'solver: answer', got '{}'",
+                    path.display(),
+                    l
+                )
+            });
+            let key = key.trim();
+            let (solver, extra_args) = match key.split_once('(') {
+                Some((solver, args)) => {
+                    let args = args.strip_suffix(')').unwrap_or_else(|| {
+                        panic!("{}: unclosed '(' in '{}'", path.display(), key)
+                    });
+                    (solver.trim(), Some(args.to_string()))
+                }
+                None => (key, None),
+            };
+            Answer {
+                solver: solver.to_string(),
+                extra_args,
+                expected: expected.trim().to_string(),
+            }
+        })
+        .collect()
+}
+
+// file names can have dashes and such, test names can't
+fn test_name(stem: &str, solver: &str) -> String {
+    let mut name: String = stem
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    format!("{}_{}", name, solver)
+}
+
+fn render_day(dir: &Path) -> String {
+    let mut examples: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "txt"))
+        .collect();
+    examples.sort();
+
+    let mut tests = String::new();
+    for example in examples {
+        let expected_path = example.with_extension("expected");
+        if !expected_path.exists() {
+            println!(
+                "cargo:warning={} has no {}, it isn't tested",
+                example.display(),
+                expected_path.display()
+            );
+            continue;
+        }
+        let stem = example.file_stem().unwrap().to_string_lossy();
+        for answer in parse_expected(&expected_path) {
+            let args = match &answer.extra_args {
+                Some(extra) => format!("input, {}", extra),
+                None => "input".to_string(),
+            };
+            write!(
+                tests,
+                "\n    #[test]\n    fn {}() {{\n        \
+                 let input = include_str!({:?});\n        \
+                 assert_eq!({}({}).to_string(), {:?});\n    }}\n",
+                test_name(&stem, &answer.solver),
+                example.display().to_string(),
+                answer.solver,
+                args,
+                answer.expected
+            )
+            .unwrap();
+        }
+    }
+    // a day without any answers yet gets an empty module, the import would
+    // be unused
+    let mut source = String::from(
+        "// generated by build.rs from the example files, don't edit\n\
+         mod examples {\n",
+    );
+    if !tests.is_empty() {
+        source.push_str("    use super::*;\n");
+        source.push_str(&tests);
+    }
+    source.push_str("}\n");
+    source
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let examples_dir = Path::new(&manifest_dir).join("examples");
+    let out_dir = Path::new(&env::var("OUT_DIR").unwrap()).join("examples");
+    println!("cargo:rerun-if-changed=examples");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let Ok(days) = fs::read_dir(&examples_dir) else {
+        return;
+    };
+    for day in days {
+        let day = day.unwrap().path();
+        if !day.is_dir() {
+            continue;
+        }
+        let name = day.file_name().unwrap().to_string_lossy().into_owned();
+        fs::write(out_dir.join(format!("{}.rs", name)), render_day(&day))
+            .unwrap();
+    }
+}
@@ -0,0 +1,342 @@
+use advent_of_code_2025::client::{self, Client, Fetched};
+use advent_of_code_2025::generate::{self, Rng};
+use advent_of_code_2025::graph;
+use advent_of_code_2025::submit::{self, Attempt, History};
+use advent_of_code_2025::{registry, scaffold};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, process};
+
+const USAGE: &str = "Usage:
+    aoc new <day> [--html <saved puzzle page>]
+    aoc fetch <day> [--base-url <url>] [--session <cookie>]
+    aoc submit <day> <part> [--answer <answer>] [--base-url <url>]
+               [--session <cookie>]
+    aoc gen <day> [--size <n>] [--seed <seed>] [--out <file>]
+    aoc mandatory <from> <to> [--input <file>]";
+
+// aoc new adds the day here, it's also how the crate root is recognised
+const REGISTRY_PATH: &str = "src/registry.rs";
+
+// returns the value after --name and removes both from args
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let i = args.iter().position(|a| a == name)?;
+    if i + 1 >= args.len() {
+        eprintln!("Missing value after {}", name);
+        process::exit(2);
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    Some(value)
+}
+
+fn parse_day(day: Option<&String>) -> u32 {
+    match day.and_then(|d| d.parse().ok()) {
+        Some(day) if (1..=25).contains(&day) => day,
+        _ => {
+            eprintln!("Expected a day between 1 and 25\n{}", USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+// --base-url and --session are taken out of args, the client itself is only
+// built once the rest of the arguments were validated
+fn client_options(args: &mut Vec<String>) -> (Option<String>, Option<String>) {
+    (
+        take_option(args, "--base-url"),
+        take_option(args, "--session"),
+    )
+}
+
+fn configure_client(
+    (base_url, session): (Option<String>, Option<String>),
+) -> Client {
+    Client::configure(base_url, session).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    })
+}
+
+// Writes the day's files, the registry goes last so a day only gets
+// registered once everything else is in place. Every file written is added
+// to written, so they can be removed again if a later step fails
+fn write_day(
+    day: u32,
+    example: String,
+    written: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    let path = scaffold::day_path(day);
+    let (example_path, expected_path) = scaffold::example_paths(day);
+    fs::write(&path, scaffold::render_day(day))?;
+    written.push(path.into());
+    fs::create_dir_all(Path::new(&example_path).parent().unwrap())?;
+    fs::write(&example_path, example)?;
+    written.push(example_path.into());
+    fs::write(&expected_path, scaffold::EXPECTED_TEMPLATE)?;
+    written.push(expected_path.into());
+
+    let input = client::input_path(Path::new("./inputs/"), day);
+    if !input.exists() {
+        fs::write(&input, "")?;
+        written.push(input);
+    }
+
+    let source = fs::read_to_string(REGISTRY_PATH)?;
+    match scaffold::register_day(&source, day) {
+        Some(source) => fs::write(REGISTRY_PATH, source),
+        None => Ok(()),
+    }
+}
+
+fn new_day(mut args: Vec<String>) {
+    let html = take_option(&mut args, "--html");
+    let day = parse_day(args.first());
+    // every path is relative to the crate root
+    if !Path::new(REGISTRY_PATH).exists() {
+        eprintln!(
+            "Can't find {}, run aoc new from the crate root",
+            REGISTRY_PATH
+        );
+        process::exit(1);
+    }
+    let path = scaffold::day_path(day);
+    let (example_path, expected_path) = scaffold::example_paths(day);
+    // existing files are never overwritten
+    for target in [&path, &example_path, &expected_path] {
+        if Path::new(target).exists() {
+            eprintln!("{} already exists", target);
+            process::exit(1);
+        }
+    }
+
+    let example = html.map(|html_path| {
+        let html = fs::read_to_string(&html_path).unwrap_or_else(|e| {
+            eprintln!("Error reading {}: {}", html_path, e);
+            process::exit(1);
+        });
+        scaffold::extract_example(&html).unwrap_or_else(|| {
+            eprintln!("Couldn't find an example in {}", html_path);
+            process::exit(1);
+        })
+    });
+
+    let mut written = Vec::new();
+    match write_day(day, example.unwrap_or_default(), &mut written) {
+        Ok(_) => println!("Created {}", path),
+        Err(e) => {
+            // nothing half made is left behind, so the day can be retried
+            for file in written.iter() {
+                let _ = fs::remove_file(file);
+            }
+            // only goes if it's empty, so only if it was made here
+            let _ = fs::remove_dir(Path::new(&example_path).parent().unwrap());
+            eprintln!("Error creating day {:02}: {}", day, e);
+            process::exit(1);
+        }
+    }
+}
+
+fn fetch(mut args: Vec<String>) {
+    let (base_url, session) = client_options(&mut args);
+    let day = parse_day(args.first());
+    let client = || Client::configure(base_url, session);
+    match client::fetch_cached(client, Path::new("./inputs/"), day) {
+        Ok(Fetched::Cached(path)) => {
+            println!(
+                "Day {:02} input already cached at {}",
+                day,
+                path.display()
+            )
+        }
+        Ok(Fetched::Downloaded(path)) => {
+            println!("Day {:02} input saved to {}", day, path.display())
+        }
+        Err(e) => {
+            eprintln!("Error fetching day {:02} input: {}", day, e);
+            process::exit(1);
+        }
+    }
+}
+
+fn submit(mut args: Vec<String>) {
+    let options = client_options(&mut args);
+    let answer = take_option(&mut args, "--answer");
+    let day = parse_day(args.first());
+    let part: u32 = match args.get(1).and_then(|p| p.parse().ok()) {
+        Some(part @ (1 | 2)) => part,
+        _ => {
+            eprintln!("Expected part 1 or 2\n{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    // without --answer the day's solver is run to get it
+    let answer = answer.unwrap_or_else(|| {
+        if !registry::has_solver(day) {
+            eprintln!("There's no solver for day {:02} yet", day);
+            process::exit(1);
+        }
+        println!("Running day{:02}...", day);
+        match submit::run_solver(day, part) {
+            Ok(Some(answer)) => answer,
+            Ok(None) => {
+                eprintln!(
+                    "day{:02} didn't print an answer for part {}",
+                    day, part
+                );
+                process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Error running the solver: {}", e);
+                process::exit(1);
+            }
+        }
+    });
+
+    let history_path = Path::new(submit::HISTORY_FILE);
+    let mut history = History::load(history_path).unwrap_or_else(|e| {
+        eprintln!("Error reading {}: {}", submit::HISTORY_FILE, e);
+        process::exit(1);
+    });
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock before 1970")
+        .as_secs();
+    if let Err(refusal) = history.check(day, part, &answer, now) {
+        eprintln!("Not submitting {}: {}", answer, refusal);
+        process::exit(1);
+    }
+
+    let client = configure_client(options);
+    println!("Submitting {} for day {:02} part {}", answer, day, part);
+    let body = client
+        .submit_answer(day, part, &answer)
+        .unwrap_or_else(|e| {
+            eprintln!("Error submitting: {}", e);
+            process::exit(1);
+        });
+    let outcome = submit::parse_response(&body);
+    println!("{}", outcome);
+
+    let attempt = Attempt {
+        day,
+        part,
+        answer,
+        outcome,
+        timestamp: now,
+        cooldown: submit::parse_cooldown(&body),
+    };
+    if let Err(e) = history.record(history_path, attempt) {
+        eprintln!("Error writing {}: {}", submit::HISTORY_FILE, e);
+        process::exit(1);
+    }
+}
+
+// parses the value of --name, exits if it isn't a number
+fn take_number(args: &mut Vec<String>, name: &str) -> Option<u64> {
+    take_option(args, name).map(|value| {
+        value.parse().unwrap_or_else(|_| {
+            eprintln!("Expected a number after {}, got '{}'", name, value);
+            process::exit(2);
+        })
+    })
+}
+
+fn gen_input(mut args: Vec<String>) {
+    let size = take_number(&mut args, "--size").unwrap_or(100) as usize;
+    // without a seed one is made up, it's printed so the input can be
+    // generated again
+    let seed = take_number(&mut args, "--seed").unwrap_or_else(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Clock before 1970")
+            .as_nanos() as u64;
+        eprintln!("Using seed {}", seed);
+        seed
+    });
+    let out = take_option(&mut args, "--out");
+    let day = parse_day(args.first());
+
+    let Some(input) = generate::puzzle_input(day, size, &mut Rng::new(seed))
+    else {
+        eprintln!("There's no input generator for day {:02}", day);
+        process::exit(1);
+    };
+    match out {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, input) {
+                eprintln!("Error writing {}: {}", path, e);
+                process::exit(1);
+            }
+        }
+        None => print!("{}", input),
+    }
+}
+
+// What every path between two devices of a day 11 style graph goes through,
+// the real day 11 input unless another one is given
+fn mandatory(mut args: Vec<String>) {
+    let path = take_option(&mut args, "--input")
+        .unwrap_or_else(|| "inputs/day11.txt".to_string());
+    let (Some(from), Some(to)) = (args.first(), args.get(1)) else {
+        eprintln!("Expected the two devices\n{}", USAGE);
+        process::exit(2);
+    };
+    let input = fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("Error reading {}: {}", path, e);
+        process::exit(1);
+    });
+    let (names, devices) = graph::parse_named(&input).unwrap_or_else(|e| {
+        eprintln!("Bad graph in {}, {}", path, e);
+        process::exit(1);
+    });
+    let id = |name: &str| match names.get(name) {
+        Some(id) => id as usize,
+        None => {
+            eprintln!("There's no device called {} in {}", name, path);
+            process::exit(1);
+        }
+    };
+
+    let Some(found) = graph::mandatory(&devices, id(from), id(to)) else {
+        println!("There are no paths from {} to {}", from, to);
+        return;
+    };
+    let name = |node: usize| names.name(node as u32);
+    println!(
+        "Every path from {} to {} goes through {} devices:",
+        from,
+        to,
+        found.nodes.len()
+    );
+    for &node in found.nodes.iter() {
+        println!("    {}", name(node));
+    }
+    println!("and {} edges:", found.edges.len());
+    for &(a, b) in found.edges.iter() {
+        println!("    {} -> {}", name(a), name(b));
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    }
+    let command = args.remove(0);
+    match command.as_str() {
+        "new" => new_day(args),
+        "fetch" => fetch(args),
+        "submit" => submit(args),
+        "gen" => gen_input(args),
+        "mandatory" => mandatory(args),
+        _ => {
+            eprintln!("Unknown command '{}'\n{}", command, USAGE);
+            process::exit(2);
+        }
+    }
+}
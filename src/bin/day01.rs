@@ -1,6 +1,22 @@
 use advent_of_code_2025::utils;
+use std::io::{self, BufRead};
 
+// a rotation like R21 is ('R', 21)
+fn parse_rotation(line: &str) -> (char, i32) {
+    let direction = line.chars().next().unwrap();
+    let amount: i32 = line[1..].parse().unwrap();
+    (direction, amount)
+}
+
+// the whole-string versions are what the example tests use
+#[cfg(test)]
 fn part_one(input: &str) -> u32 {
+    part_one_from(input.as_bytes()).expect("Reading a string can't fail")
+}
+
+// every rotation only needs the dial state left by the previous one, so the
+// input is streamed line by line instead of read whole
+fn part_one_from<R: BufRead>(reader: R) -> io::Result<u32> {
     // Every line has a denominator for the direction of rotation and the amount
     // of steps to rotate, like R21, would be rotate right 21 times
     // There are 100 positions, from 0 to 99, so we can do a modulo 100 and
@@ -9,9 +25,8 @@ fn part_one(input: &str) -> u32 {
     // after a rotation
     let mut rotator_state = 50;
     let mut zero_stops = 0;
-    for line in input.lines() {
-        let direction = line.chars().next().unwrap();
-        let amount: i32 = line[1..].parse().unwrap();
+    for rotation in utils::records(reader, parse_rotation) {
+        let (direction, amount) = rotation?;
 
         match direction {
             'R' => {
@@ -28,17 +43,21 @@ fn part_one(input: &str) -> u32 {
         }
     }
 
-    zero_stops
+    Ok(zero_stops)
 }
 
+#[cfg(test)]
 fn part_two(input: &str) -> u32 {
+    part_two_from(input.as_bytes()).expect("Reading a string can't fail")
+}
+
+fn part_two_from<R: BufRead>(reader: R) -> io::Result<u32> {
     // Same thing as part one, but we have to count every time the pointer
     // passes by 0 too
     let mut rotator_state = 50;
     let mut zero_stops: u32 = 0;
-    for line in input.lines() {
-        let direction = line.chars().next().unwrap();
-        let amount: i32 = line[1..].parse().unwrap();
+    for rotation in utils::records(reader, parse_rotation) {
+        let (direction, amount) = rotation?;
 
         match direction {
             'R' => {
@@ -73,14 +92,21 @@ fn part_two(input: &str) -> u32 {
         // }
     }
 
-    zero_stops
+    Ok(zero_stops)
 }
 
 fn main() {
-    match utils::read_input("day01.txt") {
-        Ok(input) => {
-            println!("Day 01 - Part 1: {}", part_one(input.as_str()));
-            println!("Day 01 - Part 2: {}", part_two(input.as_str()));
+    // both parts stream the file, so it never has to fit in memory
+    let answers = utils::open_input("day01.txt")
+        .and_then(part_one_from)
+        .and_then(|one| {
+            let two = utils::open_input("day01.txt").and_then(part_two_from)?;
+            Ok((one, two))
+        });
+    match answers {
+        Ok((one, two)) => {
+            println!("Day 01 - Part 1: {}", one);
+            println!("Day 01 - Part 2: {}", two);
         }
         Err(e) => {
             eprintln!("Error reading input file: {}", e);
@@ -88,3 +114,54 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate::{self, Rotation};
+    use advent_of_code_2025::prop;
+
+    // turns the dial one click at a time
+    fn clicks_through_zero(rotations: &[Rotation]) -> u32 {
+        let mut dial: i64 = 50;
+        let mut zeros = 0;
+        for r in rotations {
+            for _ in 0..r.steps {
+                dial = (dial + if r.left { -1 } else { 1 }).rem_euclid(100);
+                if dial == 0 {
+                    zeros += 1;
+                }
+            }
+        }
+        zeros
+    }
+
+    advent_of_code_2025::example_tests!();
+
+    #[test]
+    fn test_streaming_matches_string() {
+        let input = generate::render_rotations(&generate::rotations(
+            &mut generate::Rng::new(1),
+            500,
+        ));
+        // a tiny buffer so the rotations get split across reads
+        let reader = || io::BufReader::with_capacity(16, input.as_bytes());
+        assert_eq!(part_one_from(reader()).unwrap(), part_one(&input));
+        assert_eq!(part_two_from(reader()).unwrap(), part_two(&input));
+    }
+
+    #[test]
+    fn prop_part_two_matches_clicking() {
+        prop::check(
+            200,
+            |rng| {
+                let count = rng.range(1, 20) as usize;
+                generate::rotations(rng, count)
+            },
+            |rotations| {
+                let input = generate::render_rotations(rotations);
+                part_two(&input) == clicks_through_zero(rotations)
+            },
+        );
+    }
+}
@@ -1,15 +1,23 @@
+use advent_of_code_2025::parallel::{self, Execution};
 use advent_of_code_2025::utils;
+use std::env;
 
 fn part_one(input: &str) -> u64 {
+    part_one_with(input, Execution::default())
+}
+
+fn part_one_with(input: &str, execution: Execution) -> u64 {
     // The file contains ranges (start-end) separated by commas, like
     // 10-20,30-540
     // We have to identify the IDs in the ranges that are
     // "made only of some sequence of digits repeated twice"
     // Finally simply sum all of them and return the value
-    let mut acc: u64 = 0;
-    for range in input.split(",") {
+    // The ranges don't depend on eachother so each one can be summed apart
+    let ranges: Vec<&str> = input.split(",").collect();
+    parallel::sum(execution, ranges.len(), |r| {
+        let mut acc: u64 = 0;
         // just do an unwrap since every range is complete
-        let range_vector = range.split_once("-").unwrap();
+        let range_vector = ranges[r].split_once("-").unwrap();
         let start: u64 =
             range_vector.0.parse().expect("Failed to parse start value");
         // println!("{:?}", start);
@@ -23,16 +31,21 @@ fn part_one(input: &str) -> u64 {
                 acc += i;
             }
         }
-    }
-    acc
+        acc
+    })
 }
 
 fn part_two(input: &str) -> u64 {
+    part_two_with(input, Execution::default())
+}
+
+fn part_two_with(input: &str, execution: Execution) -> u64 {
     // Now an ID is invalid if the digits repeat at least twice
-    let mut acc: u64 = 0;
-    for range in input.split(",") {
+    let ranges: Vec<&str> = input.split(",").collect();
+    parallel::sum(execution, ranges.len(), |r| {
+        let mut acc: u64 = 0;
         // just do an unwrap since every range is complete
-        let range_vector = range.split_once("-").unwrap();
+        let range_vector = ranges[r].split_once("-").unwrap();
         let start: u64 =
             range_vector.0.parse().expect("Failed to parse start value");
         // println!("{:?}", start);
@@ -60,8 +73,8 @@ fn part_two(input: &str) -> u64 {
                 acc += i;
             }
         }
-    }
-    acc
+        acc
+    })
 }
 
 fn main() {
@@ -69,6 +82,14 @@ fn main() {
         Ok(input) => {
             println!("Day 02 - Part 1: {}", part_one(input.as_str()));
             println!("Day 02 - Part 2: {}", part_two(input.as_str()));
+            if env::args().any(|a| a == "--speedup") {
+                parallel::report_speedup("Day 02 - Part 1", |e| {
+                    part_one_with(input.as_str(), e)
+                });
+                parallel::report_speedup("Day 02 - Part 2", |e| {
+                    part_two_with(input.as_str(), e)
+                });
+            }
         }
         Err(e) => {
             eprintln!("Error reading input file: {}", e);
@@ -80,23 +101,40 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code_2025::generate;
+    use advent_of_code_2025::prop;
 
-    const EXAMPLE_STRING: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-
-    #[test]
-    fn test_part1_example() {
-        assert_eq!(part_one(EXAMPLE_STRING), 1227775554);
+    // some prefix of the id repeated at least twice makes up the whole id
+    fn is_repeated(id: u64) -> bool {
+        let id = id.to_string();
+        (1..=id.len() / 2).any(|l| {
+            id.len().is_multiple_of(l) && id[..l].repeat(id.len() / l) == id
+        })
     }
 
-    #[test]
-    fn test_part2_example() {
-        assert_eq!(part_two(EXAMPLE_STRING), 4174379265);
-    }
+    advent_of_code_2025::example_tests!();
+    advent_of_code_2025::parallel_tests!(2, 20, part_one_with, part_two_with);
 
     #[test]
-    fn test_part2_simple() {
-        assert_eq!(part_two("1188511885-1188511885"), 1188511885);
+    fn prop_part_two_matches_repeat_check() {
+        prop::check(
+            100,
+            |rng| {
+                let count = rng.range(1, 5) as usize;
+                generate::id_ranges(rng, count, 2000)
+            },
+            |ranges| {
+                let input = generate::render_id_ranges(ranges);
+                let expected: u64 = ranges
+                    .iter()
+                    .flat_map(|r| r.start..=r.end)
+                    .filter(|&id| is_repeated(id))
+                    .sum();
+                part_two(&input) == expected
+            },
+        );
     }
+
     // #[test]
     // fn test_bad_add() {
     //     // This assert would fire and test will fail.
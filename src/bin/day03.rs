@@ -1,76 +1,137 @@
+use advent_of_code_2025::parallel::{self, Execution};
 use advent_of_code_2025::utils;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::{env, process};
 
-fn part_one(input: &str) -> u64 {
-    let mut acc: u64 = 0;
-    for line in input.lines() {
-        // Here filter map consumes the None part if the character is not a digit
-        let line_digits: Vec<u64> = line
-            .chars()
-            .filter_map(|c| c.to_digit(10))
-            .map(|d| d as u64)
-            .collect();
-        let mut left: usize = 0;
-        // Find the largest digit and the second largest, put them together from left to right
-        for (i, d) in line_digits[0..line_digits.len() - 1].iter().enumerate() {
-            if *d > line_digits[left] {
-                left = i;
-            }
+// the largest joltage turning on two batteries of the bank
+fn bank_joltage_two(line: &str) -> u64 {
+    // Here filter map consumes the None part if the character is not a digit
+    let line_digits: Vec<u64> = line
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| d as u64)
+        .collect();
+    let mut left: usize = 0;
+    // Find the largest digit and the second largest, put them together from left to right
+    for (i, d) in line_digits[0..line_digits.len() - 1].iter().enumerate() {
+        if *d > line_digits[left] {
+            left = i;
         }
-        let mut right: usize = left + 1;
-        for i in left + 1..line_digits.len() {
-            if line_digits[i] > line_digits[right] {
-                right = i;
-            }
+    }
+    let mut right: usize = left + 1;
+    for i in left + 1..line_digits.len() {
+        if line_digits[i] > line_digits[right] {
+            right = i;
         }
-        acc += line_digits[left] * 10 + line_digits[right];
     }
-    acc
+    line_digits[left] * 10 + line_digits[right]
 }
 
-fn part_two(input: &str) -> u64 {
-    let mut acc: u64 = 0;
-    for line in input.lines() {
-        // Here filter map consumes the None part if the character is not a digit
-        let line_digits: Vec<u64> = line
-            .chars()
-            .filter_map(|c| c.to_digit(10))
-            .map(|d| d as u64)
-            .collect();
-        // for this one twelve batteries are turned on, so have to always let space at the end
-        // when checking for largest number
-        let mut indices = [0; 12];
-        let mut small_acc = 0;
-        let mut turning_on = 0;
-        while turning_on < 12 {
-            for i in
-                indices[turning_on]..line_digits.len() - (12 - turning_on - 1)
-            {
-                if line_digits[i] > line_digits[indices[turning_on]] {
-                    indices[turning_on] = i;
-                }
+// the same with twelve batteries
+fn bank_joltage_twelve(line: &str) -> u64 {
+    // Here filter map consumes the None part if the character is not a digit
+    let line_digits: Vec<u64> = line
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| d as u64)
+        .collect();
+    // for this one twelve batteries are turned on, so have to always let space at the end
+    // when checking for largest number
+    let mut indices = [0; 12];
+    let mut small_acc = 0;
+    let mut turning_on = 0;
+    while turning_on < 12 {
+        for i in indices[turning_on]..line_digits.len() - (12 - turning_on - 1)
+        {
+            if line_digits[i] > line_digits[indices[turning_on]] {
+                indices[turning_on] = i;
             }
-            // set the next starting point
-            if turning_on < 11 {
-                indices[turning_on + 1] = indices[turning_on] + 1;
-            }
-            small_acc *= 10;
-            small_acc += line_digits[indices[turning_on]];
-            turning_on += 1;
         }
-        acc += small_acc;
+        // set the next starting point
+        if turning_on < 11 {
+            indices[turning_on + 1] = indices[turning_on] + 1;
+        }
+        small_acc *= 10;
+        small_acc += line_digits[indices[turning_on]];
+        turning_on += 1;
     }
-    acc
+    small_acc
+}
+
+// a whole input at once, in parallel when the feature is on
+fn part_one(input: &str) -> u64 {
+    part_one_with(input, Execution::default())
+}
+
+// every bank is solved on its own, so they can go in parallel
+fn part_one_with(input: &str, execution: Execution) -> u64 {
+    let lines: Vec<&str> = input.lines().collect();
+    parallel::sum(execution, lines.len(), |l| bank_joltage_two(lines[l]))
+}
+
+// or streamed one bank at a time, without ever holding the whole input
+fn part_one_from<R: BufRead>(reader: R) -> io::Result<u64> {
+    utils::records(reader, bank_joltage_two).sum()
+}
+
+fn part_two(input: &str) -> u64 {
+    part_two_with(input, Execution::default())
 }
 
+fn part_two_with(input: &str, execution: Execution) -> u64 {
+    let lines: Vec<&str> = input.lines().collect();
+    parallel::sum(execution, lines.len(), |l| bank_joltage_twelve(lines[l]))
+}
+
+fn part_two_from<R: BufRead>(reader: R) -> io::Result<u64> {
+    utils::records(reader, bank_joltage_twelve).sum()
+}
+
+// every streaming solver reads the file on its own, the others get the whole
+// input
+type Solver = fn(BufReader<File>) -> io::Result<u64>;
+type WholeSolver = fn(&str) -> u64;
+
 fn main() {
-    match utils::read_input("day03.txt") {
-        Ok(input) => {
-            println!("Day 03 - Part 1: {}", part_one(input.as_str()));
-            println!("Day 03 - Part 2: {}", part_two(input.as_str()));
+    // with the parallel feature all the banks are read in to be split across
+    // the cores, and --speedup compares that with solving them one by one.
+    // Otherwise they're streamed from the file
+    let file = "day03.txt";
+    let speedup = env::args().any(|a| a == "--speedup");
+    if speedup && !cfg!(feature = "parallel") {
+        eprintln!("--speedup needs the parallel feature");
+        process::exit(2);
+    }
+    let input = if cfg!(feature = "parallel") {
+        match utils::read_input(file) {
+            Ok(input) => Some(input),
+            Err(e) => return utils::report_input_error(file, &e),
         }
-        Err(e) => {
-            eprintln!("Error reading input file: {}", e);
-            eprintln!("Make sure 'inputs/day03.txt' exists.");
+    } else {
+        None
+    };
+    if let (true, Some(input)) = (speedup, &input) {
+        parallel::report_speedup("Day 03 - Part 1", |e| {
+            part_one_with(input, e)
+        });
+        parallel::report_speedup("Day 03 - Part 2", |e| {
+            part_two_with(input, e)
+        });
+    }
+
+    let solvers: [(&str, WholeSolver, Solver); 2] = [
+        ("Part 1", part_one, part_one_from),
+        ("Part 2", part_two, part_two_from),
+    ];
+    for (label, whole, streamed) in solvers {
+        let answer = match &input {
+            Some(input) => Ok(whole(input)),
+            None => utils::open_input(file).and_then(streamed),
+        };
+        match answer {
+            Ok(answer) => println!("Day 03 - {}: {}", label, answer),
+            Err(e) => return utils::report_input_error(file, &e),
         }
     }
 }
@@ -78,28 +139,18 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code_2025::generate::{self, Rng};
 
-    const EXAMPLE_STRING: &str =
-        "987654321111111\n811111111111119\n234234234234278\n818181911112111";
-
-    #[test]
-    fn test_part1_example() {
-        assert_eq!(part_one(EXAMPLE_STRING), 357);
-    }
-
-    #[test]
-    fn test_part1_simple() {
-        assert_eq!(part_one("987654321111111"), 98);
-    }
-
-    #[test]
-    fn test_part2_example() {
-        assert_eq!(part_two(EXAMPLE_STRING), 3121910778619);
-    }
+    advent_of_code_2025::example_tests!();
+    advent_of_code_2025::parallel_tests!(3, 50, part_one_with, part_two_with);
 
     #[test]
-    fn test_part2_simple() {
-        assert_eq!(part_two("987654321111111"), 987654321111);
+    fn test_streaming_matches_string() {
+        let input = generate::puzzle_input(3, 50, &mut Rng::new(3)).unwrap();
+        // a tiny buffer so the banks get split across reads
+        let reader = || io::BufReader::with_capacity(16, input.as_bytes());
+        assert_eq!(part_one_from(reader()).unwrap(), part_one(&input));
+        assert_eq!(part_two_from(reader()).unwrap(), part_two(&input));
     }
 
     // #[test]
@@ -1,4 +1,6 @@
+use advent_of_code_2025::bitset::BitSet;
 use advent_of_code_2025::utils;
+use std::{env, fs, io, path::Path};
 
 fn part_one(input: &str) -> u64 {
     // There is a grid of rolls of paper represented by '@', and empty spaces represented by '.',
@@ -53,9 +55,19 @@ fn part_one(input: &str) -> u64 {
 }
 
 fn part_two(input: &str) -> u64 {
+    part_two_waves(input, |_, _, _| {})
+}
+
+fn part_two_waves(
+    input: &str,
+    mut on_wave: impl FnMut(usize, &[Vec<bool>], &[(usize, usize)]),
+) -> u64 {
     // for part two we have to remove the rolls of paper we identify
     // and in the next iteration identify the new rolls of paper accesible
     // and remove them too, repeating this, then return the total removed
+    // on_wave gets called for every wave with the map as it was before the
+    // wave and the positions that are about to be removed in it
+    let mut wave = 0;
     let mut printing_department_map: Vec<Vec<bool>> = input
         .lines()
         .map(|l| l.chars().map(|c| c == '@').collect())
@@ -108,17 +120,252 @@ fn part_two(input: &str) -> u64 {
                 }
             }
         }
+        if last_removed > 0 {
+            wave += 1;
+            on_wave(wave, &printing_department_map, &last_removed_positions);
+        }
         acc += last_removed;
     }
 
     acc
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Neighbourhood {
+    Four,  // up, down, left and right
+    Eight, // the four above plus the diagonals
+}
+
+impl Neighbourhood {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::Four => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Neighbourhood::Eight => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+fn part_two_worklist(
+    input: &str,
+    threshold: u8,
+    neighbourhood: Neighbourhood,
+) -> u64 {
+    // Same result as part two, but instead of rescanning the whole map on
+    // every wave, the neighbour counts are computed once and every removal
+    // decrements the counts of the rolls around it. A roll only goes into the
+    // worklist when its count drops below the threshold, so every cell is
+    // queued at most once and the whole thing is O(cells)
+    let lines: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
+    let rows = lines.len();
+    // as wide as the widest row, shorter rows are padded with empty space
+    let cols = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+
+    // the occupancy map, one bit per cell
+    let mut rolls = BitSet::with_capacity(rows * cols);
+    for (i, l) in lines.iter().enumerate() {
+        for (j, &c) in l.iter().enumerate() {
+            if c == b'@' {
+                rolls.insert(i * cols + j);
+            }
+        }
+    }
+
+    let neighbours_of = |idx: usize| {
+        let (i, j) = ((idx / cols) as isize, (idx % cols) as isize);
+        neighbourhood.offsets().iter().filter_map(move |&(li, lj)| {
+            let (ei, ej) = (i + li, j + lj);
+            if ei < 0 || ej < 0 || ei as usize >= rows || ej as usize >= cols {
+                None
+            } else {
+                Some(ei as usize * cols + ej as usize)
+            }
+        })
+    };
+
+    let mut neighbour_counts: Vec<u8> = vec![0; rows * cols];
+    let mut queued = BitSet::with_capacity(rows * cols);
+    let mut worklist: Vec<usize> = Vec::new();
+    for idx in rolls.iter() {
+        neighbour_counts[idx] =
+            neighbours_of(idx).filter(|&n| rolls.contains(n)).count() as u8;
+        if neighbour_counts[idx] < threshold {
+            queued.insert(idx);
+            worklist.push(idx);
+        }
+    }
+
+    let mut acc = 0;
+    while let Some(idx) = worklist.pop() {
+        rolls.remove(idx);
+        acc += 1;
+        for n in neighbours_of(idx) {
+            if !rolls.contains(n) {
+                continue;
+            }
+            neighbour_counts[n] -= 1;
+            if neighbour_counts[n] < threshold && queued.insert(n) {
+                worklist.push(n);
+            }
+        }
+    }
+
+    acc
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FrameFormat {
+    Ascii,
+    Ppm,
+}
+
+// one cell of the frame is drawn as a square of PPM_SCALE x PPM_SCALE pixels
+const PPM_SCALE: usize = 4;
+
+fn render_ascii_frame(
+    wave: usize,
+    map: &[Vec<bool>],
+    removed: &[(usize, usize)],
+) -> String {
+    // rolls are '@', empty spaces are '.' and the rolls removed in this wave
+    // are 'x', with a header line with the wave number and count
+    let mut grid: Vec<Vec<u8>> = map
+        .iter()
+        .map(|row| row.iter().map(|&r| if r { b'@' } else { b'.' }).collect())
+        .collect();
+    for &(i, j) in removed.iter() {
+        grid[i][j] = b'x';
+    }
+
+    let mut frame = format!("wave {}: removed {}\n", wave, removed.len());
+    for row in grid.iter() {
+        frame.push_str(str::from_utf8(row).unwrap());
+        frame.push('\n');
+    }
+    frame
+}
+
+fn render_ppm_frame(map: &[Vec<bool>], removed: &[(usize, usize)]) -> Vec<u8> {
+    // binary PPM (P6), white background, gray rolls and red removed rolls.
+    // The image is as wide as the widest row, shorter rows are padded with
+    // background
+    let rows = map.len();
+    let cols = map.iter().map(|r| r.len()).max().unwrap_or(0);
+    let mut colors: Vec<[u8; 3]> = vec![[255, 255, 255]; rows * cols];
+    for (i, row) in map.iter().enumerate() {
+        for (j, &r) in row.iter().enumerate() {
+            if r {
+                colors[i * cols + j] = [80, 80, 80];
+            }
+        }
+    }
+    for &(i, j) in removed.iter() {
+        colors[i * cols + j] = [220, 30, 30];
+    }
+
+    let mut frame =
+        format!("P6\n{} {}\n255\n", cols * PPM_SCALE, rows * PPM_SCALE)
+            .into_bytes();
+    for i in 0..rows * PPM_SCALE {
+        for j in 0..cols * PPM_SCALE {
+            frame.extend_from_slice(
+                &colors[(i / PPM_SCALE) * cols + j / PPM_SCALE],
+            );
+        }
+    }
+    frame
+}
+
+fn export_waves(
+    input: &str,
+    dir: &Path,
+    format: FrameFormat,
+) -> io::Result<u64> {
+    // every wave gets its own numbered file, so the directory can be fed
+    // straight into something like ffmpeg or an image viewer
+    fs::create_dir_all(dir)?;
+    let mut result = Ok(());
+    let removed = part_two_waves(input, |wave, map, removed| {
+        if result.is_err() {
+            return;
+        }
+        println!("Wave {:>4}: removed {}", wave, removed.len());
+        result = match format {
+            FrameFormat::Ascii => fs::write(
+                dir.join(format!("wave_{:04}.txt", wave)),
+                render_ascii_frame(wave, map, removed),
+            ),
+            FrameFormat::Ppm => fs::write(
+                dir.join(format!("wave_{:04}.ppm", wave)),
+                render_ppm_frame(map, removed),
+            ),
+        };
+    });
+    result.map(|_| removed)
+}
+
 fn main() {
+    // day04 [--frames <dir>] [--ppm] exports every removal wave of part two
+    // day04 --worklist [--threshold <n>] [--four] also prints the worklist
+    // version
+    let args: Vec<String> = env::args().skip(1).collect();
+    let threshold: u8 = args
+        .iter()
+        .position(|a| a == "--threshold")
+        .map(|i| {
+            args.get(i + 1)
+                .and_then(|t| t.parse().ok())
+                .expect("Missing or invalid number after --threshold")
+        })
+        .unwrap_or(4);
+    let worklist = args.iter().any(|a| a == "--worklist");
+    let neighbourhood = if args.iter().any(|a| a == "--four") {
+        Neighbourhood::Four
+    } else {
+        Neighbourhood::Eight
+    };
+    let frames_dir = args
+        .iter()
+        .position(|a| a == "--frames")
+        .map(|i| args.get(i + 1).expect("Missing directory after --frames"));
+    let format = if args.iter().any(|a| a == "--ppm") {
+        FrameFormat::Ppm
+    } else {
+        FrameFormat::Ascii
+    };
+
     match utils::read_input("day04.txt") {
         Ok(input) => {
             println!("Day 04 - Part 1: {}", part_one(input.as_str()));
-            println!("Day 04 - Part 2: {}", part_two(input.as_str()));
+            match frames_dir {
+                Some(dir) => {
+                    match export_waves(input.as_str(), Path::new(dir), format) {
+                        Ok(removed) => {
+                            println!("Day 04 - Part 2: {}", removed)
+                        }
+                        Err(e) => {
+                            eprintln!("Error writing frames to {}: {}", dir, e)
+                        }
+                    }
+                }
+                None => {
+                    println!("Day 04 - Part 2: {}", part_two(input.as_str()))
+                }
+            }
+            if worklist {
+                println!(
+                    "Day 04 - Part 2 worklist: {}",
+                    part_two_worklist(input.as_str(), threshold, neighbourhood)
+                );
+            }
         }
         Err(e) => {
             eprintln!("Error reading input file: {}", e);
@@ -131,12 +378,10 @@ fn main() {
 mod tests {
     use super::*;
 
-    const EXAMPLE_STRING: &str = "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.";
+    const EXAMPLE_STRING: &str =
+        include_str!("../../examples/day04/puzzle.txt");
 
-    #[test]
-    fn test_part1_example() {
-        assert_eq!(part_one(EXAMPLE_STRING), 13);
-    }
+    advent_of_code_2025::example_tests!();
 
     // #[test]
     // fn test_part1_simple() {
@@ -144,8 +389,68 @@ mod tests {
     // }
 
     #[test]
-    fn test_part2_example() {
-        assert_eq!(part_two(EXAMPLE_STRING), 43);
+    fn test_part2_waves_example() {
+        let mut wave_counts: Vec<usize> = Vec::new();
+        let removed = part_two_waves(EXAMPLE_STRING, |wave, _, removed| {
+            assert_eq!(wave, wave_counts.len() + 1);
+            wave_counts.push(removed.len());
+        });
+        assert_eq!(removed, 43);
+        assert_eq!(wave_counts, vec![13, 12, 7, 5, 2, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_part2_worklist_example() {
+        assert_eq!(
+            part_two_worklist(EXAMPLE_STRING, 4, Neighbourhood::Eight),
+            part_two(EXAMPLE_STRING)
+        );
+    }
+
+    #[test]
+    fn test_part2_worklist_four_neighbours() {
+        // with only 4 neighbours every roll has at most 4 around it, so a
+        // threshold of 5 removes everything and 1 removes only lonely rolls
+        assert_eq!(part_two_worklist("@@\n@@", 5, Neighbourhood::Four), 4);
+        assert_eq!(part_two_worklist("@@\n@@", 2, Neighbourhood::Four), 0);
+        assert_eq!(part_two_worklist("@.@\n.@.", 1, Neighbourhood::Four), 3);
+    }
+
+    #[test]
+    fn test_part2_worklist_ragged_rows() {
+        // the second row is longer than the first, none of it gets cut off
+        let ragged = ".\n@@@@@\n@@";
+        assert_eq!(
+            part_two_worklist(ragged, 4, Neighbourhood::Eight),
+            part_two(ragged)
+        );
+        assert_eq!(part_two(ragged), 7);
+    }
+
+    #[test]
+    fn test_ascii_frame() {
+        let map = vec![vec![true, false], vec![true, true]];
+        assert_eq!(
+            render_ascii_frame(1, &map, &[(1, 0)]),
+            "wave 1: removed 1\n@.\nx@\n"
+        );
+    }
+
+    #[test]
+    fn test_ppm_frame() {
+        // the second row is shorter, its missing cell is background
+        let map = vec![vec![true, false], vec![true]];
+        let frame = render_ppm_frame(&map, &[(1, 0)]);
+        let header = "P6\n8 8\n255\n";
+        assert_eq!(&frame[..header.len()], header.as_bytes());
+        let pixels = &frame[header.len()..];
+        assert_eq!(pixels.len(), 8 * 8 * 3);
+        let pixel = |x: usize, y: usize| &pixels[(y * 8 + x) * 3..][..3];
+        assert_eq!(pixel(0, 0), [80, 80, 80]);
+        assert_eq!(pixel(3, 3), [80, 80, 80]);
+        assert_eq!(pixel(4, 0), [255, 255, 255]);
+        assert_eq!(pixel(0, 4), [220, 30, 30]);
+        assert_eq!(pixel(7, 7), [255, 255, 255]);
     }
     //
     // #[test]
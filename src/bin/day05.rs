@@ -118,23 +118,13 @@ fn main() {
 mod tests {
     use super::*;
 
-    const EXAMPLE_STRING: &str =
-        "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32";
-
-    #[test]
-    fn test_part1_example() {
-        assert_eq!(part_one(EXAMPLE_STRING), 3);
-    }
+    advent_of_code_2025::example_tests!();
 
     // #[test]
     // fn test_part1_simple() {
     //     assert_eq!(part_one("987654321111111"), 98);
     // }
 
-    #[test]
-    fn test_part2_example() {
-        assert_eq!(part_two(EXAMPLE_STRING), 14);
-    }
     //
     // #[test]
     // fn test_part2_simple() {
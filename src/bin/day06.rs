@@ -86,13 +86,17 @@ fn part_two(input: &str) -> u64 {
                 last_digit_amount += 1;
             }
         }
+        // the last problem has no operator after it, so it's counted here
+        largest_digit_amount = largest_digit_amount.max(last_digit_amount);
         digit_amounts.push(last_digit_amount);
     }
     // println!("{:?}", digit_amounts);
 
     let n_cols = cols;
 
-    let t_cols = n_rows;
+    // every problem gets one number per digit column, so the stride is the
+    // widest problem and not the amount of rows
+    let t_cols = largest_digit_amount;
     let t_rows = n_cols;
 
     let mut numbers: Vec<u64> = vec![0; t_rows * largest_digit_amount];
@@ -250,13 +254,25 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code_2025::generate;
+    use advent_of_code_2025::prop;
 
-    const EXAMPLE_STRING: &str =
-        "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+    advent_of_code_2025::example_tests!();
 
     #[test]
-    fn test_part1_example() {
-        assert_eq!(part_one(EXAMPLE_STRING), 4277556);
+    fn prop_part_two_matches_alt() {
+        prop::check(
+            200,
+            |rng| {
+                let problems = rng.range(1, 6) as usize;
+                let rows = rng.range(1, 4) as usize;
+                generate::worksheet(rng, problems, rows)
+            },
+            |sheet| {
+                let input = sheet.to_string();
+                part_two(&input) == part_two_alt(&input)
+            },
+        );
     }
 
     // #[test]
@@ -264,15 +280,6 @@ mod tests {
     //     assert_eq!(part_one("987654321111111"), 98);
     // }
 
-    #[test]
-    fn test_part2_example() {
-        assert_eq!(part_two(EXAMPLE_STRING), 3263827);
-    }
-
-    #[test]
-    fn test_part2_alt_example() {
-        assert_eq!(part_two_alt(EXAMPLE_STRING), 3263827);
-    }
     //
     // #[test]
     // fn test_part2_simple() {
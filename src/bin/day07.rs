@@ -1,22 +1,26 @@
+use advent_of_code_2025::bitset::BitSet;
 use advent_of_code_2025::utils;
 
 fn part_one(input: &str) -> u64 {
     let mut times_split = 0;
-    let mut current_lasers: Vec<bool> =
-        vec![false; input.lines().next().map(|l| l.len()).unwrap()];
+    // one bit per column telling if there's a laser going down it
+    let mut current_lasers =
+        BitSet::with_capacity(input.lines().next().map(|l| l.len()).unwrap());
 
     for l in input.lines() {
         for (j, c) in l.bytes().enumerate() {
             match c {
                 b'.' => {}
                 b'S' => {
-                    current_lasers[j] = true;
+                    current_lasers.insert(j);
                 }
                 b'^' => {
-                    if current_lasers[j] {
-                        current_lasers[j] = false;
-                        current_lasers[j - 1] = true;
-                        current_lasers[j + 1] = true;
+                    // the splitter stops the laser, if there was one it
+                    // goes on both sides
+                    let hit = current_lasers.remove(j);
+                    if hit {
+                        current_lasers.insert(j - 1);
+                        current_lasers.insert(j + 1);
                         times_split += 1;
                     }
                 }
@@ -71,38 +75,13 @@ fn main() {
 mod tests {
     use super::*;
 
-    const EXAMPLE_STRING: &str = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............
-";
-
-    #[test]
-    fn test_part1_example() {
-        assert_eq!(part_one(EXAMPLE_STRING), 21);
-    }
+    advent_of_code_2025::example_tests!();
 
     // #[test]
     // fn test_part1_simple() {
     //     assert_eq!(part_one("987654321111111"), 98);
     // }
 
-    #[test]
-    fn test_part2_example() {
-        assert_eq!(part_two(EXAMPLE_STRING), 40);
-    }
     //
     // #[test]
     // fn test_part2_alt_example() {
@@ -1,5 +1,4 @@
-use advent_of_code_2025::utils::{self, HasX, HasY, HasZ, Vector};
-use std::array;
+use advent_of_code_2025::utils::{self, HasX, Vector};
 
 #[allow(dead_code)] // Suppress the warning for the whole struct
 #[derive(Debug, Copy, Clone)]
@@ -8,19 +7,11 @@ struct JBConnection {
     distance_squared: u64,
 }
 
-fn euclidean_distance_squared(a: &Vector<u64, 3>, b: &Vector<u64, 3>) -> u64 {
-    let c1 = b.x().abs_diff(*a.x());
-    let c2 = b.y().abs_diff(*a.y());
-    let c3 = b.z().abs_diff(*a.z());
-    c1 * c1 + c2 * c2 + c3 * c3
-}
-
 fn read_jbox_positions(input: &str) -> Vec<Vector<u64, 3>> {
     let mut jbox_positions: Vec<Vector<u64, 3>> = Vec::new();
     for l in input.lines() {
-        let mut parts = l.split(',').map(|s| s.trim().parse::<u64>().unwrap());
         let jbox_position: Vector<u64, 3> =
-            array::from_fn(|_| parts.next().unwrap()).into();
+            l.parse().expect("Malformed position");
         jbox_positions.push(jbox_position);
     }
     jbox_positions
@@ -48,15 +39,12 @@ fn part_one(input: &str, connections_to_take: usize) -> u64 {
         for j in i + 1..jbox_positions.len() {
             jbox_connections.push(JBConnection {
                 jbox_idxs: (i, j),
-                distance_squared: euclidean_distance_squared(
-                    &jbox_positions[i],
-                    &jbox_positions[j],
-                ),
+                distance_squared: jbox_positions[i]
+                    .distance_squared(jbox_positions[j]),
             })
         }
     }
-    jbox_connections
-        .sort_by(|a, b| a.distance_squared.cmp(&b.distance_squared));
+    jbox_connections.sort_by_key(|c| c.distance_squared);
 
     // ds_roots has to be same size as the initial jbox_positions vector for the algorithm
     let mut ds_roots: Vec<usize> = (0..jbox_positions.len()).collect();
@@ -97,15 +85,12 @@ fn part_two(input: &str) -> u64 {
         for j in i + 1..jbox_positions.len() {
             jbox_connections.push(JBConnection {
                 jbox_idxs: (i, j),
-                distance_squared: euclidean_distance_squared(
-                    &jbox_positions[i],
-                    &jbox_positions[j],
-                ),
+                distance_squared: jbox_positions[i]
+                    .distance_squared(jbox_positions[j]),
             })
         }
     }
-    jbox_connections
-        .sort_by(|a, b| a.distance_squared.cmp(&b.distance_squared));
+    jbox_connections.sort_by_key(|c| c.distance_squared);
 
     let mut ds_roots: Vec<usize> = (0..jbox_positions.len()).collect();
     let mut ds_sizes: Vec<u64> = vec![1; jbox_positions.len()];
@@ -160,41 +145,13 @@ fn main() {
 mod tests {
     use super::*;
 
-    const EXAMPLE_STRING: &str = "162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689";
-
-    #[test]
-    fn test_part1_example() {
-        assert_eq!(part_one(EXAMPLE_STRING, 10), 40);
-    }
+    advent_of_code_2025::example_tests!();
 
     // #[test]
     // fn test_part1_simple() {
     //     assert_eq!(part_one("987654321111111"), 98);
     // }
 
-    #[test]
-    fn test_part2_example() {
-        assert_eq!(part_two(EXAMPLE_STRING), 25272);
-    }
     //
     // #[test]
     // fn test_part2_alt_example() {
@@ -1,36 +1,38 @@
+use advent_of_code_2025::parallel::{self, Execution};
 use advent_of_code_2025::utils::{self, HasX, HasY, Vector};
-use std::{array, cmp::max, cmp::min};
+use std::env;
 
 fn read_red_positions(input: &str) -> Vec<Vector<u64, 2>> {
     let mut red_positions: Vec<Vector<u64, 2>> = Vec::new();
     for l in input.lines() {
-        let mut parts = l.split(',').map(|s| s.trim().parse::<u64>().unwrap());
         let red_position: Vector<u64, 2> =
-            array::from_fn(|_| parts.next().unwrap()).into();
+            l.parse().expect("Malformed position");
         red_positions.push(red_position);
     }
     red_positions
 }
 
 fn part_one(input: &str) -> u64 {
+    part_one_with(input, Execution::default())
+}
+
+// the pairs are split up by their first corner, every first corner is
+// checked against the ones after it on its own
+fn part_one_with(input: &str, execution: Execution) -> u64 {
     let red_positions: Vec<Vector<u64, 2>> = read_red_positions(input);
 
-    let mut max_area = 0;
-    for i in 0..red_positions.len() {
+    parallel::max(execution, red_positions.len(), |i| {
+        let mut max_area = 0;
         for j in (i + 1)..red_positions.len() {
-            let sides: Vector<u64, 2> = [
-                red_positions[i].x().abs_diff(*red_positions[j].x()) + 1,
-                red_positions[i].y().abs_diff(*red_positions[j].y()) + 1,
-            ]
-            .into();
+            let sides: Vector<u64, 2> =
+                red_positions[i].abs_diff(red_positions[j]) + [1, 1].into();
             let area = sides.x() * sides.y();
             if area > max_area {
                 max_area = area;
             }
         }
-    }
-
-    max_area
+        max_area
+    })
 }
 
 fn edge_cuts_rectangle(
@@ -67,19 +69,23 @@ fn edge_cuts_rectangle(
 }
 
 fn part_two(input: &str) -> u64 {
+    part_two_with(input, Execution::default())
+}
+
+fn part_two_with(input: &str, execution: Execution) -> u64 {
     let red_positions: Vec<Vector<u64, 2>> = read_red_positions(input);
     // polygon is pre built so the list comes already in
     // the final shape of the edges
-    let mut max_area = 0;
-    for i in 0..red_positions.len() {
+    parallel::max(execution, red_positions.len(), |i| {
+        let mut max_area = 0;
         for j in (i + 1)..red_positions.len() {
             let corner_one = red_positions[i];
             let corner_three = red_positions[j];
 
-            let r_right = max(*corner_one.x(), *corner_three.x());
-            let r_left = min(*corner_one.x(), *corner_three.x());
-            let r_top = max(*corner_one.y(), *corner_three.y());
-            let r_bottom = min(*corner_one.y(), *corner_three.y());
+            let top_right = corner_one.max(corner_three);
+            let bottom_left = corner_one.min(corner_three);
+            let (r_right, r_top) = (*top_right.x(), *top_right.y());
+            let (r_left, r_bottom) = (*bottom_left.x(), *bottom_left.y());
 
             // check if the middle point is in the polygon by counting the
             // amount of crossings to the right, if the number is odd, then the
@@ -122,9 +128,8 @@ fn part_two(input: &str) -> u64 {
                 }
             }
         }
-    }
-
-    max_area
+        max_area
+    })
 }
 
 fn main() {
@@ -132,6 +137,14 @@ fn main() {
         Ok(input) => {
             println!("Day 09 - Part 1: {}", part_one(input.as_str()));
             println!("Day 09 - Part 2: {}", part_two(input.as_str()));
+            if env::args().any(|a| a == "--speedup") {
+                parallel::report_speedup("Day 09 - Part 1", |e| {
+                    part_one_with(input.as_str(), e)
+                });
+                parallel::report_speedup("Day 09 - Part 2", |e| {
+                    part_two_with(input.as_str(), e)
+                });
+            }
         }
         Err(e) => {
             eprintln!("Error reading input file: {}", e);
@@ -144,30 +157,14 @@ fn main() {
 mod tests {
     use super::*;
 
-    const EXAMPLE_STRING: &str = "7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3";
-
-    #[test]
-    fn test_part1_example() {
-        assert_eq!(part_one(EXAMPLE_STRING), 50);
-    }
+    advent_of_code_2025::example_tests!();
+    advent_of_code_2025::parallel_tests!(9, 40, part_one_with, part_two_with);
 
     // #[test]
     // fn test_part1_simple() {
     //     assert_eq!(part_one("987654321111111"), 98);
     // }
 
-    #[test]
-    fn test_part2_example() {
-        assert_eq!(part_two(EXAMPLE_STRING), 24);
-    }
-
     // #[test]
     // fn test_part2_example_custom() {
     //     assert_eq!(part_two(EXAMPLE_STRING_CUSTOM), 24);
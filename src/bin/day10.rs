@@ -1,60 +1,105 @@
+use advent_of_code_2025::bitset::BitSet;
+use advent_of_code_2025::line_grammar;
+use advent_of_code_2025::parallel::{self, Execution};
+use advent_of_code_2025::parse::{
+    Cursor, ParseError, delimited, list, map, repeated,
+};
+use advent_of_code_2025::search;
 use advent_of_code_2025::utils;
 use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::{env, process};
 
-// use a bitmask to represent the state
-#[derive(Debug, Clone)]
-struct Machine {
-    desired_indicator_lights: u64, // bitmask of the final state
-    button_wiring_schematics: Vec<u64>, // vec of bitmasks
-    joltage_requirements: Vec<u64>, // vec of joltage requirements
+// we are gonna fill the mask from the right to the left, but the input has
+// index 0 at the left, either way it should work the same
+fn lights_mask(cursor: &mut Cursor) -> Result<u64, ParseError> {
+    let lights = cursor.take_while(|c| c == b'.' || c == b'#');
+    Ok(lights
+        .iter()
+        .enumerate()
+        .filter(|&(_, &c)| c == b'#')
+        .fold(0, |mask, (i, _)| mask | 1 << i))
 }
 
-fn read_input(input: &str) -> Vec<Machine> {
-    let mut machines: Vec<Machine> = Vec::new();
-    for l in input.lines() {
-        let (lights_wrap, rest) =
-            l.split_once(' ').expect("Missing space after lights");
-        let (buttons_wrap, joltage_wrap) =
-            rest.rsplit_once(' ').expect("Missing space before joltage");
-
-        let mut desired_indicator_lights: u64 = 0;
-        // we are gonna fill the mask from the right to the left, but the input
-        // has index 0 at the left, either way it should work the same
-        for (i, c) in lights_wrap.chars().skip(1).enumerate() {
-            if c == '#' {
-                desired_indicator_lights |= 1 << i;
-            }
-        }
+fn wiring_mask(wires: Vec<u64>) -> u64 {
+    wires.iter().fold(0, |mask, wire| mask | 1 << wire)
+}
 
-        let mut button_wiring_schematics: Vec<u64> = Vec::new();
-        for button in buttons_wrap.split_whitespace() {
-            let mut button_wiring_schematic: u64 = 0;
-            for idx in
-                button.trim_matches(|bs| bs == '(' || bs == ')').split(',')
-            {
-                button_wiring_schematic |= 1 << idx.parse::<u64>().unwrap();
-            }
-            button_wiring_schematics.push(button_wiring_schematic);
-        }
+// use a bitmask to represent the state, a whole line looks like
+// [.##.] (3) (1,3) (2) {3,5,4,7}
+line_grammar! {
+    #[derive(Debug, Clone)]
+    struct Machine {
+        // bitmask of the final state
+        desired_indicator_lights: u64 = delimited("[", lights_mask, "]"),
+        // vec of bitmasks
+        button_wiring_schematics: Vec<u64> = repeated(delimited(
+            " (",
+            map(list(b',', Cursor::uint), wiring_mask),
+            ")",
+        )),
+        " {",
+        // vec of joltage requirements
+        joltage_requirements: Vec<u64> = list(b',', Cursor::uint),
+        "}",
+    }
+}
 
-        let mut joltage_requirements: Vec<u64> = Vec::new();
-        for joltage in joltage_wrap
-            .trim_matches(|js| js == '{' || js == '}')
-            .split(',')
-        {
-            joltage_requirements.push(joltage.parse::<u64>().unwrap());
+fn read_input(input: &str) -> Vec<Machine> {
+    let mut cursor = Cursor::new(input);
+    let mut machines = Vec::new();
+    while !cursor.is_empty() {
+        match Machine::parse(&mut cursor) {
+            Ok(machine) => machines.push(machine),
+            Err(e) => panic!("Bad machine in the input, {}", e),
         }
-
-        machines.push(Machine {
-            desired_indicator_lights,
-            button_wiring_schematics,
-            joltage_requirements,
-        })
     }
     machines
 }
 
+// the same one line at a time, a bad line comes out as an io error
+fn machines_from<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = io::Result<Machine>> {
+    let mut line = 0;
+    utils::records(reader, move |l| {
+        line += 1;
+        Machine::parse(&mut Cursor::at_line(l, line))
+    })
+    .map(|machine| Ok(machine??))
+}
+
+// bfs over the light states, every button press flips the lights in its
+// mask, so the successors of a state are the state xor'd with every button.
+// The states are bitmasks so a bitset is enough to keep track of the visited
+// ones. Lights that are meant to be all off are already done, that takes 0
+// presses
+fn min_presses_lights(machine: &Machine) -> u64 {
+    let presses = search::bfs_with(
+        0u64,
+        BitSet::new(),
+        |&lights| {
+            machine
+                .button_wiring_schematics
+                .iter()
+                .map(move |button| lights ^ button)
+        },
+        |&lights| lights == machine.desired_indicator_lights,
+    )
+    .expect("Desired lights are unreachable")
+    .cost;
+
+    presses as u64
+}
+
+// a whole input at once, in parallel when the feature is on
 fn part_one(input: &str) -> u64 {
+    part_one_with(input, Execution::default())
+}
+
+// the machines are independent, each one is solved on its own
+fn part_one_with(input: &str, execution: Execution) -> u64 {
     let machines = read_input(input);
     // print machines buttons and desired lights as bitmask
     // for machine in machines.iter() {
@@ -64,102 +109,271 @@ fn part_one(input: &str) -> u64 {
     //     );
     // }
 
-    // VERY INNEFICIENT WITH HASHSET, BUT IT WORKS :/
-    let mut acc: u64 = 0;
-    for machine in machines.iter() {
-        // do a sort of bfs, but without storing every past state, only the
-        // last level states and the current depth level which will be the
-        // smallest amount of presses needed to reach the desired final light
-        // state when we find it somewhere
-        let mut last_states: HashSet<u64> = HashSet::new();
-        last_states.insert(0u64);
-        let mut current_level: u64 = 1;
-
-        'bfs: loop {
-            let mut next_states: HashSet<u64> = HashSet::new();
-            for button in machine.button_wiring_schematics.iter() {
-                for s in last_states.iter() {
-                    let resulting_lights = s ^ button;
-                    // println!(
-                    //     "{:b}, {:b}",
-                    //     resulting_lights, machine.desired_indicator_lights
-                    // );
-                    if resulting_lights == machine.desired_indicator_lights {
-                        break 'bfs;
-                    }
-                    next_states.insert(resulting_lights);
+    parallel::sum(execution, machines.len(), |m| {
+        min_presses_lights(&machines[m])
+    })
+}
+
+// or one machine at a time straight from the reader
+fn part_one_from<R: BufRead>(reader: R) -> io::Result<u64> {
+    machines_from(reader)
+        .map(|machine| Ok(min_presses_lights(&machine?)))
+        .sum()
+}
+
+// now each button's number adds one to the respective joltage counter
+// ignore the logic for the indicator lights
+fn min_presses_bfs(machine: &Machine) -> u64 {
+    let mut last_states: HashSet<Vec<u64>> = HashSet::new();
+    last_states.insert(vec![0; machine.joltage_requirements.len()]);
+    let mut current_level: u64 = 1;
+
+    'bfs: loop {
+        let mut next_states: HashSet<Vec<u64>> = HashSet::new();
+        for button in machine.button_wiring_schematics.iter() {
+            for s in last_states.iter() {
+                let mut resulting_joltages: Vec<u64> = s.clone();
+                for (i, resulting_joltage) in
+                    resulting_joltages.iter_mut().enumerate()
+                {
+                    *resulting_joltage += (button >> i) & 0b1;
+                }
+
+                let over_limit = resulting_joltages
+                    .iter()
+                    .zip(&machine.joltage_requirements)
+                    .any(|(rj, jr)| rj > jr);
+
+                if resulting_joltages == machine.joltage_requirements {
+                    break 'bfs;
+                }
+                if !over_limit {
+                    next_states.insert(resulting_joltages);
                 }
             }
-            current_level += 1;
-            last_states = next_states;
         }
-
-        acc += current_level;
+        current_level += 1;
+        last_states = next_states;
     }
-    acc
+
+    current_level
 }
 
 fn part_two_bfs(input: &str) -> u64 {
-    // now each button's number adds one to the respective joltage counter
-    // ignore the logic for the indicator lights
+    part_two_bfs_with(input, Execution::default())
+}
+
+fn part_two_bfs_with(input: &str, execution: Execution) -> u64 {
     let machines = read_input(input);
+    parallel::sum(execution, machines.len(), |m| min_presses_bfs(&machines[m]))
+}
+
+fn part_two_bfs_from<R: BufRead>(reader: R) -> io::Result<u64> {
+    machines_from(reader)
+        .map(|machine| Ok(min_presses_bfs(&machine?)))
+        .sum()
+}
+
+// divides the row by the gcd of its entries so the numbers stay small during
+// the elimination
+fn reduce_row(row: &mut [i64]) {
+    let gcd = row.iter().fold(0, |a, &b| {
+        let (mut a, mut b) = (a, b.abs());
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    });
+    if gcd > 1 {
+        row.iter_mut().for_each(|x| *x /= gcd);
+    }
+}
+
+// Tries every value of the free buttons, the presses of the pivot buttons
+// follow from them. Gives up on a branch once the free presses alone are
+// already worse than the best so far
+fn search_free_presses(
+    rows: &[Vec<i64>],
+    pivots: &[usize],
+    free: &[(usize, i64)],
+    presses: &mut [i64],
+    free_total: i64,
+    best: &mut Option<i64>,
+) {
+    if best.is_some_and(|b| free_total >= b) {
+        return;
+    }
+    if let Some((&(button, bound), rest)) = free.split_first() {
+        for p in 0..=bound {
+            presses[button] = p;
+            search_free_presses(
+                rows,
+                pivots,
+                rest,
+                presses,
+                free_total + p,
+                best,
+            );
+        }
+        presses[button] = 0;
+        return;
+    }
+
+    let rhs_col = presses.len();
+    let mut total = free_total;
+    for (row, &pivot) in rows.iter().zip(pivots) {
+        let rhs = row[rhs_col]
+            - (0..rhs_col)
+                .filter(|&c| c != pivot)
+                .map(|c| row[c] * presses[c])
+                .sum::<i64>();
+        if rhs % row[pivot] != 0 || rhs / row[pivot] < 0 {
+            return;
+        }
+        total += rhs / row[pivot];
+    }
+    if best.is_none_or(|b| total < b) {
+        *best = Some(total);
+    }
+}
 
-    let mut acc: u64 = 0;
-    for machine in machines.iter() {
-        let mut last_states: HashSet<Vec<u64>> = HashSet::new();
-        last_states.insert(vec![0; machine.joltage_requirements.len()]);
-        let mut current_level: u64 = 1;
-
-        'bfs: loop {
-            let mut next_states: HashSet<Vec<u64>> = HashSet::new();
-            for button in machine.button_wiring_schematics.iter() {
-                for s in last_states.iter() {
-                    let mut resulting_joltages: Vec<u64> = s.clone();
-                    for (i, resulting_joltage) in
-                        resulting_joltages.iter_mut().enumerate()
-                    {
-                        *resulting_joltage += (button >> i) & 0b1;
-                    }
-
-                    let over_limit = resulting_joltages
-                        .iter()
-                        .zip(&machine.joltage_requirements)
-                        .any(|(rj, jr)| rj > jr);
-
-                    if resulting_joltages == machine.joltage_requirements {
-                        break 'bfs;
-                    }
-                    if !over_limit {
-                        next_states.insert(resulting_joltages);
-                    }
+fn min_presses_linalg(machine: &Machine) -> Option<u64> {
+    // with x_j the presses of button j, the joltages are A x = b where A has
+    // a 1 where button j is wired to counter i. Gaussian elimination (kept in
+    // integers) leaves every counter's row with a single pivot button, the
+    // buttons that don't get a pivot are free and have to be searched
+    let counters = machine.joltage_requirements.len();
+    let buttons = machine.button_wiring_schematics.len();
+    let mut rows: Vec<Vec<i64>> = (0..counters)
+        .map(|i| {
+            let mut row: Vec<i64> = machine
+                .button_wiring_schematics
+                .iter()
+                .map(|b| ((b >> i) & 1) as i64)
+                .collect();
+            row.push(machine.joltage_requirements[i] as i64);
+            row
+        })
+        .collect();
+
+    let mut pivots: Vec<usize> = Vec::new();
+    for col in 0..buttons {
+        let r = pivots.len();
+        if r == counters {
+            break;
+        }
+        let Some(p) = (r..counters).find(|&i| rows[i][col] != 0) else {
+            continue;
+        };
+        rows.swap(r, p);
+        let pivot_row = rows[r].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i != r && row[col] != 0 {
+                let factor = row[col];
+                for (x, &y) in row.iter_mut().zip(&pivot_row) {
+                    *x = *x * pivot_row[col] - y * factor;
                 }
+                reduce_row(row);
             }
-            current_level += 1;
-            last_states = next_states;
         }
-
-        acc += current_level;
+        pivots.push(col);
+    }
+    // the leftover rows are all zeros, unless the joltages are impossible
+    if rows[pivots.len()..].iter().any(|row| row[buttons] != 0) {
+        return None;
     }
-    acc
+    rows.truncate(pivots.len());
+
+    // a button can't be pressed more often than the smallest joltage it adds
+    // to, or that counter goes over
+    let free: Vec<(usize, i64)> = (0..buttons)
+        .filter(|b| !pivots.contains(b))
+        .map(|b| {
+            let bound = (0..counters)
+                .filter(|&i| {
+                    (machine.button_wiring_schematics[b] >> i) & 1 == 1
+                })
+                .map(|i| machine.joltage_requirements[i] as i64)
+                .min()
+                .unwrap_or(0);
+            (b, bound)
+        })
+        .collect();
+
+    let mut best = None;
+    let mut presses = vec![0; buttons];
+    search_free_presses(&rows, &pivots, &free, &mut presses, 0, &mut best);
+    best.map(|b| b as u64)
+}
+
+fn part_two_linalg(input: &str) -> u64 {
+    part_two_linalg_with(input, Execution::default())
 }
 
-fn part_two_linalg(_input: &str) -> u64 {
-    32
+fn part_two_linalg_with(input: &str, execution: Execution) -> u64 {
+    let machines = read_input(input);
+    parallel::sum(execution, machines.len(), |m| {
+        min_presses_linalg(&machines[m]).expect("Joltages are unreachable")
+    })
 }
 
+fn part_two_linalg_from<R: BufRead>(reader: R) -> io::Result<u64> {
+    machines_from(reader)
+        .map(|machine| {
+            Ok(
+                min_presses_linalg(&machine?)
+                    .expect("Joltages are unreachable"),
+            )
+        })
+        .sum()
+}
+
+// every streaming solver reads the file on its own, the others get the whole
+// input
+type Solver = fn(BufReader<File>) -> io::Result<u64>;
+type WholeSolver = fn(&str) -> u64;
+
 fn main() {
-    match utils::read_input("day10.txt") {
-        Ok(input) => {
-            println!("Day 10 - Part 1: {}", part_one(input.as_str()));
-            println!("Day 10 - Part 2 bfs: {}", part_two_bfs(input.as_str()));
-            println!(
-                "Day 10 - Part 2 bfs: {}",
-                part_two_linalg(input.as_str())
-            );
+    // with the parallel feature all the machines are read in to be split
+    // across the cores, and --speedup compares that with solving them one by
+    // one (the bfs is left out, it's slow enough to run once). Otherwise
+    // they're streamed from the file
+    let file = "day10.txt";
+    let speedup = env::args().any(|a| a == "--speedup");
+    if speedup && !cfg!(feature = "parallel") {
+        eprintln!("--speedup needs the parallel feature");
+        process::exit(2);
+    }
+    let input = if cfg!(feature = "parallel") {
+        match utils::read_input(file) {
+            Ok(input) => Some(input),
+            Err(e) => return utils::report_input_error(file, &e),
         }
-        Err(e) => {
-            eprintln!("Error reading input file: {}", e);
-            eprintln!("Make sure 'inputs/day10.txt' exists.");
+    } else {
+        None
+    };
+    if let (true, Some(input)) = (speedup, &input) {
+        parallel::report_speedup("Day 10 - Part 1", |e| {
+            part_one_with(input, e)
+        });
+        parallel::report_speedup("Day 10 - Part 2 linalg", |e| {
+            part_two_linalg_with(input, e)
+        });
+    }
+
+    let solvers: [(&str, WholeSolver, Solver); 3] = [
+        ("Part 1", part_one, part_one_from),
+        ("Part 2 bfs", part_two_bfs, part_two_bfs_from),
+        ("Part 2 linalg", part_two_linalg, part_two_linalg_from),
+    ];
+    for (label, whole, streamed) in solvers {
+        let answer = match &input {
+            Some(input) => Ok(whole(input)),
+            None => utils::open_input(file).and_then(streamed),
+        };
+        match answer {
+            Ok(answer) => println!("Day 10 - {}: {}", label, answer),
+            Err(e) => return utils::report_input_error(file, &e),
         }
     }
 }
@@ -167,31 +381,91 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code_2025::generate::{self, Rng};
+    use advent_of_code_2025::prop;
 
-    const EXAMPLE_STRING: &str =
-        "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
-[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
-[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+    advent_of_code_2025::example_tests!();
+    advent_of_code_2025::parallel_tests!(
+        10,
+        10,
+        part_one_with,
+        part_two_linalg_with
+    );
 
     #[test]
-    fn test_part1_example() {
-        assert_eq!(part_one(EXAMPLE_STRING), 7);
+    fn test_streaming_matches_string() {
+        let input = generate::puzzle_input(10, 10, &mut Rng::new(10)).unwrap();
+        // a tiny buffer so the machines get split across reads
+        let reader = || BufReader::with_capacity(16, input.as_bytes());
+        assert_eq!(part_one_from(reader()).unwrap(), part_one(&input));
+        // the bfs is too slow for the generated machines, the example will do
+        let example = include_str!("../../examples/day10/puzzle.txt");
+        assert_eq!(
+            part_two_bfs_from(BufReader::with_capacity(16, example.as_bytes()))
+                .unwrap(),
+            part_two_bfs(example)
+        );
+        assert_eq!(
+            part_two_linalg_from(reader()).unwrap(),
+            part_two_linalg(&input)
+        );
     }
 
-    // #[test]
-    // fn test_part1_simple() {
-    //     assert_eq!(part_one("987654321111111"), 98);
-    // }
+    #[test]
+    fn test_lights_already_off() {
+        // the search starts at the goal, so no button is pressed (the
+        // hand-written bfs used to press one twice and answer 2)
+        assert_eq!(part_one("[....] (0,1) (2) {1,1,1,0}\n"), 0);
+        assert_eq!(part_one("[....] (0,1) (2) {1,1,1,0}\n[.#] (1) {0,1}\n"), 1);
+    }
 
     #[test]
-    fn test_part2_bfs_example() {
-        assert_eq!(part_two_bfs(EXAMPLE_STRING), 33);
+    fn test_parse_errors() {
+        let bad = "[.##.] (3) (1,3) {3,5}\n[.#] (0 (1) {2,2}\n";
+        let e = machines_from(bad.as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 8: expected ')' for button_wiring_schematics, \
+             found ' '"
+        );
+    }
+
+    fn small_machines(rng: &mut Rng) -> Vec<generate::Machine> {
+        (0..rng.range(1, 3))
+            .map(|_| {
+                let counters = rng.range(1, 4) as usize;
+                let buttons = rng.range(1, 4) as usize;
+                generate::machine(rng, counters, buttons, 3)
+            })
+            .collect()
     }
 
     #[test]
-    fn test_part2_linalg_example() {
-        assert_eq!(part_two_linalg(EXAMPLE_STRING), 33);
+    fn prop_part_two_bfs_matches_linalg() {
+        prop::check(100, small_machines, |machines| {
+            let input = generate::render_machines(machines);
+            part_two_bfs(&input) == part_two_linalg(&input)
+        });
     }
+
+    #[test]
+    fn prop_part_two_linalg_is_at_most_generated_presses() {
+        // the presses the machine was generated from are one way to get there
+        prop::check(100, small_machines, |machines| {
+            let input = generate::render_machines(machines);
+            let generated: u64 =
+                machines.iter().flat_map(|m| m.presses.iter()).sum();
+            part_two_linalg(&input) <= generated
+        });
+    }
+
+    // #[test]
+    // fn test_part1_simple() {
+    //     assert_eq!(part_one("987654321111111"), 98);
+    // }
+
     // #[test]
     // fn test_part2_example_custom() {
     //     assert_eq!(part_two(EXAMPLE_STRING_CUSTOM), 24);
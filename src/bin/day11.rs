@@ -1,105 +1,317 @@
-use std::collections::VecDeque;
-
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::env;
+use std::str::FromStr;
+
+use advent_of_code_2025::generate::Rng;
+use advent_of_code_2025::graph::{self, AdjacencyList};
+use advent_of_code_2025::intern::Interner;
 use advent_of_code_2025::utils;
 
-type Device = [u8; 3];
-
-// Compressed Sparse Row (CSR)
-// the edges would have like 3 5 6 all next to eachother, in a node that has
-// edges to those indexes for example node 0. So edges[0] = 3, edges[1] = 5,
-// edges[2] = 6. And offsets[0] = 0. Then it would follow that offsets[1] = 3
-// because that's the next available spot. And we can determine the amount of
-// outgoing edges 0 has by doing offsets[1] - offsets[0]
-struct AdjacencyList {
-    edges: Vec<usize>, // vector of compacted edges outgoing from every node
-    offsets: Vec<usize>, // offsets indexing into the edges vec indicating the
-                       // start of the current node's outgoing edges
-}
-
 struct DeviceGraph {
-    devices: Vec<Device>,
+    devices: Interner,
     connections: AdjacencyList,
 }
 
-fn read_input(input: &str) -> DeviceGraph {
-    let mut devices: Vec<Device> = Vec::new();
-    // could entirely remove this storage of edges, but honestly i started out
-    // by reading the edges and devices, then moved on to a CSR becasue it's
-    // better for bfs and I don't wanna change it now cause it works
-    // after removing this, simply doing two passes over the input data would
-    // work too to fill the adjacency list
-    let mut edge_list: Vec<(usize, usize)> = Vec::new();
-
-    for l in input.lines() {
-        let (start_device_name, rest) = l
-            .split_once(':')
-            .map(|(a, b)| (a.as_bytes(), b.as_bytes()))
-            .expect("Couldn't split line at ':'");
+impl DeviceGraph {
+    fn index(&self, name: &str) -> Option<usize> {
+        self.devices.get(name).map(|id| id as usize)
+    }
 
-        assert_eq!(
-            start_device_name.len(),
-            3,
-            "device name's len is different than 3"
-        );
+    fn outputs(&self, node: usize) -> &[usize] {
+        self.connections.outputs(node)
+    }
 
-        let start_device: Device = start_device_name
-            .try_into()
-            .expect("couldn't convert into [u8; 3]");
+    // every node comes after all of its inputs, panics if there's a cycle
+    fn topological_order(&self) -> Vec<usize> {
+        let mut inputs_left = vec![0usize; self.devices.len()];
+        for node in 0..self.devices.len() {
+            for &output in self.outputs(node) {
+                inputs_left[output] += 1;
+            }
+        }
+        let mut order: Vec<usize> = (0..self.devices.len())
+            .filter(|&n| inputs_left[n] == 0)
+            .collect();
+        let mut i = 0;
+        while i < order.len() {
+            for &output in self.outputs(order[i]) {
+                inputs_left[output] -= 1;
+                if inputs_left[output] == 0 {
+                    order.push(output);
+                }
+            }
+            i += 1;
+        }
+        assert_eq!(order.len(), self.devices.len(), "The devices form a cycle");
+        order
+    }
 
-        // using a vec and linearly searching is probably faster than a hashmap
-        // for smaller device amounts
-        let start_device_id = devices
-            .iter()
-            .position(|&d| d == start_device) // iterator is consumed here
-            .unwrap_or_else(|| {
-                devices.push(start_device);
-                devices.len() - 1
-            });
-
-        for end_device_name in rest.split(|&b| b == b' ') {
-            if end_device_name.len() != 3 {
+    // the counts can outgrow a u64 long before the graph gets big, every
+    // level of two ways around doubles them
+    fn too_many_paths(&self, node: usize) -> String {
+        format!(
+            "too many paths through {} to count",
+            self.devices.name(node as u32)
+        )
+    }
+
+    // How many different paths go from the source to every node. A node's
+    // count is only final once all of its inputs are, so they're added up in
+    // topological order
+    fn path_counts(&self, source: usize) -> Result<Vec<u64>, String> {
+        let mut from_source: Vec<u64> = vec![0; self.devices.len()];
+        from_source[source] = 1;
+        for node in self.topological_order() {
+            let count = from_source[node];
+            if count == 0 {
                 continue;
             }
-            let end_device: Device = end_device_name
-                .try_into()
-                .expect("couldn't convert end device name into Device type");
+            for &output in self.outputs(node) {
+                from_source[output] = from_source[output]
+                    .checked_add(count)
+                    .ok_or_else(|| self.too_many_paths(output))?;
+            }
+        }
+        Ok(from_source)
+    }
 
-            let end_device_id = devices
-                .iter()
-                .position(|&d| d == end_device)
-                .unwrap_or_else(|| {
-                    devices.push(end_device);
-                    devices.len() - 1
-                });
+    // the other way around, how many paths go from every node to the sink
+    fn paths_to(&self, sink: usize) -> Result<Vec<u64>, String> {
+        let mut to_sink: Vec<u64> = vec![0; self.devices.len()];
+        to_sink[sink] = 1;
+        for node in self.topological_order().into_iter().rev() {
+            if node != sink {
+                to_sink[node] = self
+                    .outputs(node)
+                    .iter()
+                    .try_fold(0u64, |sum, &o| sum.checked_add(to_sink[o]))
+                    .ok_or_else(|| self.too_many_paths(node))?;
+            }
+        }
+        Ok(to_sink)
+    }
+
+    // paths going through every stop in order, from the first to the last
+    fn paths_via(&self, stops: &[usize]) -> Result<u64, String> {
+        stops.windows(2).try_fold(1u64, |paths, pair| {
+            paths
+                .checked_mul(self.path_counts(pair[0])?[pair[1]])
+                .ok_or_else(|| self.too_many_paths(pair[1]))
+        })
+    }
 
-            edge_list.push((start_device_id, end_device_id));
+    // lazily, so .take(n) stops the walk after n paths
+    fn paths(&self, source: usize, sink: usize) -> Result<Paths<'_>, String> {
+        let to_sink = self.paths_to(sink)?;
+        let (path, next_output) = if to_sink[source] > 0 {
+            (vec![source], vec![0])
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        Ok(Paths {
+            graph: self,
+            sink,
+            to_sink,
+            path,
+            next_output,
+        })
+    }
+
+    // Best first over partial paths, ordered by how long they'll be once they
+    // reach the sink. The hops left are known exactly, so the finished paths
+    // come out shortest first
+    fn shortest_paths(
+        &self,
+        source: usize,
+        sink: usize,
+        k: usize,
+    ) -> Vec<Vec<usize>> {
+        let mut hops: Vec<Option<usize>> = vec![None; self.devices.len()];
+        hops[sink] = Some(0);
+        for node in self.topological_order().into_iter().rev() {
+            if node != sink {
+                hops[node] = self
+                    .outputs(node)
+                    .iter()
+                    .filter_map(|&o| hops[o])
+                    .min()
+                    .map(|h| h + 1);
+            }
+        }
+
+        let mut heap = BinaryHeap::new();
+        if let Some(h) = hops[source] {
+            heap.push(Reverse((h, vec![source])));
+        }
+        let mut found = Vec::new();
+        while found.len() < k {
+            let Some(Reverse((_, path))) = heap.pop() else {
+                break;
+            };
+            let node = *path.last().unwrap();
+            if node == sink {
+                found.push(path);
+                continue;
+            }
+            for &output in self.outputs(node) {
+                if let Some(h) = hops[output] {
+                    let mut longer = path.clone();
+                    longer.push(output);
+                    heap.push(Reverse((path.len() + h, longer)));
+                }
+            }
         }
+        found
     }
 
-    let mut outgoing_edges_count: Vec<usize> = vec![0; devices.len()];
-    for &edge in edge_list.iter() {
-        outgoing_edges_count[edge.0] += 1;
+    // Every path is equally likely: from each node the next one is picked
+    // with a chance proportional to the paths it still has to the sink.
+    // to_sink is the table from paths_to, so it's only built once for a lot
+    // of samples
+    fn sample_path(
+        &self,
+        source: usize,
+        sink: usize,
+        to_sink: &[u64],
+        rng: &mut Rng,
+    ) -> Option<Vec<usize>> {
+        if to_sink[source] == 0 {
+            return None;
+        }
+        let mut path = vec![source];
+        let mut node = source;
+        while node != sink {
+            let mut pick = rng.range(0, to_sink[node] - 1);
+            node = *self
+                .outputs(node)
+                .iter()
+                .find(|&&output| {
+                    if pick < to_sink[output] {
+                        return true;
+                    }
+                    pick -= to_sink[output];
+                    false
+                })
+                .unwrap();
+            path.push(node);
+        }
+        Some(path)
     }
 
-    let mut connections = AdjacencyList {
-        edges: vec![0; edge_list.len()],
-        offsets: Vec::with_capacity(devices.len() + 1),
-    };
+    fn path_names(&self, path: &[usize]) -> String {
+        let names: Vec<&str> =
+            path.iter().map(|&n| self.devices.name(n as u32)).collect();
+        names.join(" -> ")
+    }
 
-    let mut total: usize = 0;
-    for &outgoing_edge_count in outgoing_edges_count.iter() {
-        connections.offsets.push(total);
-        total += outgoing_edge_count;
+    // one label and role per node
+    fn export_nodes(
+        &self,
+        options: &ExportOptions,
+    ) -> Result<Vec<(String, Role)>, String> {
+        let find = |name: &str| {
+            self.index(name)
+                .ok_or_else(|| format!("no device called {}", name))
+        };
+        let source = options.source.map(find).transpose()?;
+        let sink = options.sink.map(find).transpose()?;
+        let waypoints = options
+            .waypoints
+            .iter()
+            .map(|&w| find(w))
+            .collect::<Result<Vec<usize>, String>>()?;
+        let counts = match source {
+            Some(source) if options.path_counts => {
+                Some(self.path_counts(source)?)
+            }
+            _ => None,
+        };
+
+        Ok(self
+            .devices
+            .iter()
+            .map(|(id, name)| {
+                let node = id as usize;
+                let label = match &counts {
+                    Some(counts) => format!("{} ({})", name, counts[node]),
+                    None => name.to_string(),
+                };
+                let role = if source == Some(node) {
+                    Role::Source
+                } else if sink == Some(node) {
+                    Role::Sink
+                } else if waypoints.contains(&node) {
+                    Role::Waypoint
+                } else {
+                    Role::Plain
+                };
+                (label, role)
+            })
+            .collect())
     }
-    // last value is used just to get the amount of edges for the last node
-    connections.offsets.push(total);
 
-    let mut current_offsets_in_use = connections.offsets.clone();
-    for &edge in edge_list.iter() {
-        connections.edges[current_offsets_in_use[edge.0]] = edge.1;
-        current_offsets_in_use[edge.0] += 1;
+    // Graphviz, render with something like `dot -Tsvg`
+    fn to_dot(&self, options: &ExportOptions) -> Result<String, String> {
+        let mut dot =
+            String::from("digraph devices {\n    node [shape=box];\n");
+        for (node, (label, role)) in
+            self.export_nodes(options)?.into_iter().enumerate()
+        {
+            let label = label.replace('\\', "\\\\").replace('"', "\\\"");
+            let style = match role {
+                Role::Source => ", style=filled, fillcolor=palegreen",
+                Role::Sink => ", style=filled, fillcolor=lightcoral",
+                Role::Waypoint => ", style=filled, fillcolor=gold",
+                Role::Plain => "",
+            };
+            dot += &format!("    n{} [label=\"{}\"{}];\n", node, label, style);
+        }
+        for node in 0..self.devices.len() {
+            for &output in self.outputs(node) {
+                dot += &format!("    n{} -> n{};\n", node, output);
+            }
+        }
+        dot += "}\n";
+        Ok(dot)
     }
 
+    // Mermaid flowchart, renders on github and in most markdown viewers
+    fn to_mermaid(&self, options: &ExportOptions) -> Result<String, String> {
+        let mut mermaid = String::from("flowchart LR\n");
+        let nodes = self.export_nodes(options)?;
+        for (node, (label, _)) in nodes.iter().enumerate() {
+            let label = label.replace('"', "#quot;");
+            mermaid += &format!("    n{}[\"{}\"]\n", node, label);
+        }
+        for node in 0..self.devices.len() {
+            for &output in self.outputs(node) {
+                mermaid += &format!("    n{} --> n{}\n", node, output);
+            }
+        }
+        for (role, class, fill) in [
+            (Role::Source, "source", "#9f9"),
+            (Role::Sink, "sink", "#f99"),
+            (Role::Waypoint, "waypoint", "#fd6"),
+        ] {
+            let members: Vec<String> = (0..nodes.len())
+                .filter(|&node| nodes[node].1 == role)
+                .map(|node| format!("n{}", node))
+                .collect();
+            if !members.is_empty() {
+                mermaid += &format!("    classDef {} fill:{}\n", class, fill);
+                mermaid +=
+                    &format!("    class {} {}\n", members.join(","), class);
+            }
+        }
+        Ok(mermaid)
+    }
+}
+
+fn read_input(input: &str) -> DeviceGraph {
+    // every name gets a dense id, so they index straight into the CSR
+    let (devices, connections) = graph::parse_named(input)
+        .unwrap_or_else(|e| panic!("Bad device in the input, {}", e));
     DeviceGraph {
         devices,
         connections,
@@ -109,42 +321,13 @@ fn read_input(input: &str) -> DeviceGraph {
 fn part_one(input: &str) -> u64 {
     let device_graph = read_input(input);
 
-    let you_idx = device_graph
-        .devices
-        .iter()
-        .position(|d| d == b"you")
-        .unwrap();
-    let out_idx = device_graph
-        .devices
-        .iter()
-        .position(|d| d == b"out")
-        .unwrap();
-
-    let mut independent_paths: Vec<u64> = vec![0; device_graph.devices.len()];
-    independent_paths[you_idx] = 1;
-
-    let mut bfs_deque: VecDeque<usize> = VecDeque::new();
-    bfs_deque.push_back(you_idx);
-
-    let mut visited = vec![false; device_graph.devices.len()];
-
-    while let Some(current_idx) = bfs_deque.pop_front() {
-        let start = device_graph.connections.offsets[current_idx];
-        let end = device_graph.connections.offsets[current_idx + 1];
-        for &linked_node_idx in
-            device_graph.connections.edges[start..end].iter()
-        {
-            independent_paths[linked_node_idx] +=
-                independent_paths[current_idx];
-
-            if !visited[linked_node_idx] {
-                visited[linked_node_idx] = true;
-                bfs_deque.push_back(linked_node_idx);
-            }
-        }
-    }
+    let you_idx = device_graph.index("you").unwrap();
+    let out_idx = device_graph.index("out").unwrap();
 
-    independent_paths[out_idx]
+    let counts = device_graph
+        .path_counts(you_idx)
+        .unwrap_or_else(|e| panic!("Can't count the paths, {}", e));
+    counts[out_idx]
 }
 
 fn part_two(input: &str) -> u64 {
@@ -152,58 +335,177 @@ fn part_two(input: &str) -> u64 {
     // dac and fft, in any order
     let device_graph = read_input(input);
 
-    let (mut svr_idx, mut out_idx, mut dac_idx, mut fft_idx): (
-        Option<usize>,
-        Option<usize>,
-        Option<usize>,
-        Option<usize>,
-    ) = (None, None, None, None);
-    for (i, device) in device_graph.devices.iter().enumerate() {
-        match device.as_slice() {
-            b"svr" => svr_idx = Some(i),
-            b"out" => out_idx = Some(i),
-            b"dac" => dac_idx = Some(i),
-            b"fft" => fft_idx = Some(i),
-            _ => {}
+    let svr_idx = device_graph.index("svr").expect("Couldn't find svr idx");
+    let out_idx = device_graph.index("out").expect("Couldn't find out idx");
+    let dac_idx = device_graph.index("dac").expect("Couldn't find dac idx");
+    let fft_idx = device_graph.index("fft").expect("Couldn't find fft idx");
+
+    // the graph has no cycles so only one of the orders can have paths
+    let via = |stops: &[usize]| {
+        device_graph
+            .paths_via(stops)
+            .unwrap_or_else(|e| panic!("Can't count the paths, {}", e))
+    };
+    via(&[svr_idx, fft_idx, dac_idx, out_idx])
+        + via(&[svr_idx, dac_idx, fft_idx, out_idx])
+}
+
+// ////////////// PATHS /////////////////////
+// Walks every path from the source to the sink depth first, handing them out
+// one at a time. Nodes that can't reach the sink are never entered, so every
+// step down gets closer to a path
+struct Paths<'g> {
+    graph: &'g DeviceGraph,
+    sink: usize,
+    to_sink: Vec<u64>,
+    path: Vec<usize>,
+    next_output: Vec<usize>, // per node on the path, the next output to try
+}
+
+impl Iterator for Paths<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        loop {
+            let &node = self.path.last()?;
+            if node == self.sink {
+                let found = self.path.clone();
+                self.path.pop();
+                self.next_output.pop();
+                return Some(found);
+            }
+            let outputs = self.graph.outputs(node);
+            let tried = self.next_output.last_mut().unwrap();
+            match outputs[*tried..]
+                .iter()
+                .position(|&output| self.to_sink[output] > 0)
+            {
+                Some(skipped) => {
+                    *tried += skipped + 1;
+                    self.path.push(outputs[*tried - 1]);
+                    self.next_output.push(0);
+                }
+                None => {
+                    self.path.pop();
+                    self.next_output.pop();
+                }
+            }
         }
     }
-    let svr_idx: usize = svr_idx.expect("Couldn't find svr idx");
-    let out_idx: usize = out_idx.expect("Couldn't find out idx");
-    let dac_idx: usize = dac_idx.expect("Couldn't find dac idx");
-    let fft_idx: usize = fft_idx.expect("Couldn't find fft idx");
+}
 
-    let mut independent_paths: Vec<u64> = vec![0; device_graph.devices.len()];
-    independent_paths[svr_idx] = 1;
+// --paths <n>, --shortest <k> or --sample <n> between --source and --sink,
+// svr and out if they aren't given
+fn list_paths(input: &str, args: &[String]) -> Result<String, String> {
+    let device_graph = read_input(input);
+    let find = |flag: &str, default: &str| {
+        let name = flag_value(args, flag).unwrap_or(default);
+        device_graph
+            .index(name)
+            .ok_or_else(|| format!("no device called {}", name))
+    };
+    let source = find("--source", "svr")?;
+    let sink = find("--sink", "out")?;
+    let paths: Vec<Vec<usize>> =
+        if let Some(limit) = flag_number(args, "--paths") {
+            device_graph.paths(source, sink)?.take(limit?).collect()
+        } else if let Some(k) = flag_number(args, "--shortest") {
+            device_graph.shortest_paths(source, sink, k?)
+        } else if let Some(samples) = flag_number::<usize>(args, "--sample") {
+            // a u64 like the seeds of aoc gen, so the same seed means the same
+            // Rng everywhere
+            let seed = flag_number(args, "--seed").transpose()?.unwrap_or(0);
+            let mut rng = Rng::new(seed);
+            let to_sink = device_graph.paths_to(sink)?;
+            (0..samples?)
+                .filter_map(|_| {
+                    device_graph.sample_path(source, sink, &to_sink, &mut rng)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+    Ok(paths
+        .iter()
+        .map(|path| device_graph.path_names(path) + "\n")
+        .collect())
+}
 
-    let mut bfs_deque: VecDeque<usize> = VecDeque::new();
-    bfs_deque.push_back(svr_idx);
+// ////////////// EXPORT /////////////////////
+// What to point out when drawing the graph, every name has to be a device
+#[derive(Debug, Default)]
+struct ExportOptions<'a> {
+    source: Option<&'a str>,
+    sink: Option<&'a str>,
+    waypoints: Vec<&'a str>,
+    path_counts: bool, // label every node with its paths from the source
+}
 
-    // TODO DEFINITILY SWITCH TO A BFS AND KEEP TRACK OF WHETHER THE DAC AND
-    // FFT HAVE BEEN SEEN IN THE CURRENT PATH
+// how a node is drawn, the first role that fits wins
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Role {
+    Source,
+    Sink,
+    Waypoint,
+    Plain,
+}
 
-    let mut visited = vec![false; device_graph.devices.len()];
+// the value given after a flag, like --source svr
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(|v| v.as_str())
+}
 
-    while let Some(current_idx) = bfs_deque.pop_front() {
-        let start = device_graph.connections.offsets[current_idx];
-        let end = device_graph.connections.offsets[current_idx + 1];
-        for &linked_node_idx in
-            device_graph.connections.edges[start..end].iter()
-        {
-            independent_paths[linked_node_idx] +=
-                independent_paths[current_idx];
+// the number after a flag, parsed as whatever it's used as
+fn flag_number<T: FromStr>(
+    args: &[String],
+    flag: &str,
+) -> Option<Result<T, String>> {
+    flag_value(args, flag).map(|v| {
+        v.parse()
+            .map_err(|_| format!("{} needs a number, got {}", flag, v))
+    })
+}
 
-            if !visited[linked_node_idx] {
-                visited[linked_node_idx] = true;
-                bfs_deque.push_back(linked_node_idx);
-            }
-        }
+fn export(input: &str, args: &[String]) -> Result<String, String> {
+    let device_graph = read_input(input);
+    let options = ExportOptions {
+        source: flag_value(args, "--source"),
+        sink: flag_value(args, "--sink"),
+        waypoints: flag_value(args, "--via")
+            .map(|v| v.split(',').collect())
+            .unwrap_or_default(),
+        path_counts: args.iter().any(|a| a == "--counts"),
+    };
+    if args.iter().any(|a| a == "--mermaid") {
+        device_graph.to_mermaid(&options)
+    } else {
+        device_graph.to_dot(&options)
     }
-
-    independent_paths[out_idx]
 }
 
 fn main() {
+    // --paths, --shortest and --sample print paths instead of the answers,
+    // see list_paths. --dot or --mermaid print the graph instead, with
+    // --source <device>, --sink <device>, --via <device,device...> to
+    // highlight devices and --counts to label them with their paths from
+    // the source
+    let args: Vec<String> = env::args().collect();
+    let exporting = args.iter().any(|a| a == "--dot" || a == "--mermaid");
+    let listing = ["--paths", "--shortest", "--sample"]
+        .iter()
+        .any(|flag| args.iter().any(|a| a == flag));
     match utils::read_input("day11.txt") {
+        Ok(input) if exporting => match export(input.as_str(), &args) {
+            Ok(graph) => print!("{}", graph),
+            Err(e) => eprintln!("Can't export the graph: {}", e),
+        },
+        Ok(input) if listing => match list_paths(input.as_str(), &args) {
+            Ok(paths) => print!("{}", paths),
+            Err(e) => eprintln!("Can't list the paths: {}", e),
+        },
         Ok(input) => {
             println!("Day 11 - Part 1: {}", part_one(input.as_str()));
             println!("Day 11 - Part 2: {}", part_two(input.as_str()));
@@ -218,46 +520,215 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code_2025::generate;
+    use advent_of_code_2025::prop;
+    use std::collections::HashMap;
 
-    const EXAMPLE_STRING: &str = "aaa: you hhh
-you: bbb ccc
-bbb: ddd eee
-ccc: ddd eee fff
-ddd: ggg
-eee: out
-fff: out
-ggg: out
-hhh: ccc fff iii
-iii: out
-";
+    advent_of_code_2025::example_tests!();
 
     #[test]
-    fn test_part1_example() {
-        assert_eq!(part_one(EXAMPLE_STRING), 5);
+    fn test_long_device_names() {
+        let graph =
+            read_input("you: a longer-name\nlonger-name: out\na: out\n");
+        assert_eq!(graph.devices.len(), 4);
+        assert_eq!(graph.devices.name(1), "a");
+        assert_eq!(graph.index("longer-name"), Some(2));
+        assert_eq!(graph.connections.offsets, vec![0, 2, 3, 4, 4]);
+        assert_eq!(graph.connections.edges, vec![1, 2, 3, 3]);
+    }
+
+    const EXAMPLE_STRING: &str =
+        include_str!("../../examples/day11/puzzle2.txt");
+
+    #[test]
+    fn test_path_counts_wait_for_every_input() {
+        // bbb is reached early through aaa and again later through ccc, the
+        // paths through ccc still have to make it to out
+        let graph = read_input("aaa: bbb ccc\nccc: ddd\nddd: bbb\nbbb: out\n");
+        // ids go in the order the names show up, so aaa is 0 and out is 4
+        assert_eq!(graph.path_counts(0).unwrap()[4], 2);
+    }
+
+    #[test]
+    fn test_path_counts_too_big() {
+        // 64 diamonds in a row, 2^64 paths from n0 to out
+        let mut input = String::new();
+        for i in 0..64 {
+            input +=
+                &format!("n{i}: a{i} b{i}\na{i}: n{0}\nb{i}: n{0}\n", i + 1);
+        }
+        input += "n64: out\n";
+        let graph = read_input(&input);
+        let (start, out) =
+            (graph.index("n0").unwrap(), graph.index("out").unwrap());
+        // going forward it's n64 that gets too many, going back it's n0
+        let error = |node: &str| {
+            Err(format!("too many paths through {} to count", node))
+        };
+        assert_eq!(graph.path_counts(start).map(|c| c[out]), error("n64"));
+        assert_eq!(graph.paths_to(out).map(|c| c[start]), error("n0"));
+        assert!(graph.paths(start, out).is_err());
+        // one diamond less fits
+        let n63 = graph.index("n63").unwrap();
+        assert_eq!(graph.paths_to(n63).unwrap()[start], 1 << 63);
+    }
+
+    #[test]
+    fn test_export_dot() {
+        let graph = read_input(EXAMPLE_STRING);
+        let dot = graph
+            .to_dot(&ExportOptions {
+                source: Some("svr"),
+                sink: Some("out"),
+                waypoints: vec!["fft", "dac"],
+                path_counts: true,
+            })
+            .unwrap();
+        assert!(dot.starts_with("digraph devices {\n"));
+        assert!(dot.contains(
+            "n0 [label=\"svr (1)\", style=filled, fillcolor=palegreen];"
+        ));
+        assert!(
+            dot.contains("[label=\"fft (1)\", style=filled, fillcolor=gold]")
+        );
+        assert!(dot.contains(
+            "[label=\"out (8)\", style=filled, fillcolor=lightcoral]"
+        ));
+        assert!(dot.contains("n0 -> n1;"));
+        let edges = dot.lines().filter(|l| l.contains("->")).count();
+        assert_eq!(edges, 16);
+    }
+
+    #[test]
+    fn test_export_mermaid() {
+        let graph = read_input("you: out\n");
+        let mermaid = graph
+            .to_mermaid(&ExportOptions {
+                source: Some("you"),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            mermaid,
+            "flowchart LR\n    n0[\"you\"]\n    n1[\"out\"]\n    n0 --> n1\n    \
+             classDef source fill:#9f9\n    class n0 source\n"
+        );
+        let missing = graph.to_mermaid(&ExportOptions {
+            waypoints: vec!["dac"],
+            ..Default::default()
+        });
+        assert_eq!(missing, Err("no device called dac".to_string()));
+    }
+
+    #[test]
+    fn test_paths_of_example() {
+        let graph = read_input(EXAMPLE_STRING);
+        let (svr, out) =
+            (graph.index("svr").unwrap(), graph.index("out").unwrap());
+        let paths: Vec<String> = graph
+            .paths(svr, out)
+            .unwrap()
+            .map(|p| graph.path_names(&p))
+            .collect();
+        assert_eq!(paths.len(), 8);
+        assert_eq!(
+            paths[0],
+            "svr -> aaa -> fft -> ccc -> ddd -> hub -> fff -> ggg -> out"
+        );
+        assert_eq!(graph.paths(svr, out).unwrap().take(3).count(), 3);
+        // nothing goes back up to svr
+        assert_eq!(graph.paths(out, svr).unwrap().count(), 0);
+
+        let shortest = graph.shortest_paths(svr, out, 3);
+        assert_eq!(
+            shortest.iter().map(|p| p.len()).collect::<Vec<_>>(),
+            vec![9, 9, 9]
+        );
+    }
+
+    #[test]
+    fn test_sampling_is_uniform() {
+        let graph = read_input(EXAMPLE_STRING);
+        let (svr, out) =
+            (graph.index("svr").unwrap(), graph.index("out").unwrap());
+        let to_sink = graph.paths_to(out).unwrap();
+        let mut rng = Rng::new(11);
+        let mut seen: HashMap<Vec<usize>, usize> = HashMap::new();
+        for _ in 0..8000 {
+            let path = graph.sample_path(svr, out, &to_sink, &mut rng).unwrap();
+            *seen.entry(path).or_default() += 1;
+        }
+        // 8 paths, so about 1000 each
+        assert_eq!(seen.len(), 8);
+        assert!(
+            seen.values().all(|&n| (800..1200).contains(&n)),
+            "{:?}",
+            seen
+        );
+    }
+
+    #[test]
+    fn test_sample_seed_is_a_u64() {
+        let graph = read_input(EXAMPLE_STRING);
+        let (svr, out) =
+            (graph.index("svr").unwrap(), graph.index("out").unwrap());
+        let to_sink = graph.paths_to(out).unwrap();
+        let mut rng = Rng::new(u64::MAX);
+        let expected: String = (0..3)
+            .map(|_| {
+                let path = graph.sample_path(svr, out, &to_sink, &mut rng);
+                graph.path_names(&path.unwrap()) + "\n"
+            })
+            .collect();
+        let seed = u64::MAX.to_string();
+        let args = ["--sample", "3", "--seed", &seed].map(String::from);
+        assert_eq!(list_paths(EXAMPLE_STRING, &args), Ok(expected));
+        let args = ["--sample", "3", "--seed", "-1"].map(String::from);
+        assert_eq!(
+            list_paths(EXAMPLE_STRING, &args),
+            Err("--seed needs a number, got -1".to_string())
+        );
+    }
+
+    #[test]
+    fn prop_enumeration_matches_counts() {
+        // the graph is seeded by its size, so shrinking the size shrinks the
+        // graph
+        prop::check(
+            50,
+            |rng| rng.range(5, 40) as usize,
+            |&devices| {
+                let input = generate::device_graph(
+                    &mut Rng::new(devices as u64),
+                    devices,
+                );
+                let graph = read_input(&input);
+                let (svr, out) =
+                    (graph.index("svr").unwrap(), graph.index("out").unwrap());
+                let paths: Vec<Vec<usize>> =
+                    graph.paths(svr, out).unwrap().collect();
+                let mut lengths: Vec<usize> =
+                    paths.iter().map(|p| p.len()).collect();
+                lengths.sort();
+                let shortest: Vec<usize> = graph
+                    .shortest_paths(svr, out, 5)
+                    .iter()
+                    .map(|p| p.len())
+                    .collect();
+                paths.len() as u64 == graph.path_counts(svr).unwrap()[out]
+                    && paths.iter().all(|p| {
+                        p.windows(2)
+                            .all(|e| graph.outputs(e[0]).contains(&e[1]))
+                    })
+                    && shortest == lengths[..lengths.len().min(5)]
+            },
+        );
     }
 
     // #[test]
     // fn test_part1_simple() {
     //     assert_eq!(part_one("987654321111111"), 98);
     // }
-    const EXAMPLE_STRING_PART2: &str = "svr: aaa bbb
-aaa: fft
-fft: ccc
-bbb: tty
-tty: ccc
-ccc: ddd eee
-ddd: hub
-hub: fff
-eee: dac
-dac: fff
-fff: ggg hhh
-ggg: out
-hhh: out";
-
-    #[test]
-    fn test_part2_example() {
-        assert_eq!(part_two(EXAMPLE_STRING_PART2), 2);
-    }
 
     // #[test]
     // fn test_part2_example_custom() {
@@ -0,0 +1,306 @@
+// Dense sets of small integers, one bit per possible value. BitSet grows as
+// needed, FixedBitSet has room for 64 * W values and is Copy, so it can be
+// used directly as a search state
+
+// ////////////// WORD HELPERS /////////////////////
+// both sets are just a slice of u64 words underneath, bit i of the set is
+// bit i % 64 of word i / 64
+fn split(value: usize) -> (usize, u64) {
+    (value / 64, 1 << (value % 64))
+}
+
+fn count_ones(words: &[u64]) -> usize {
+    words.iter().map(|w| w.count_ones() as usize).sum()
+}
+
+pub struct Iter<'a> {
+    words: &'a [u64],
+    word_idx: usize,
+    current: u64,
+}
+
+impl<'a> Iter<'a> {
+    fn new(words: &'a [u64]) -> Self {
+        Iter {
+            words,
+            word_idx: 0,
+            current: words.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl Iterator for Iter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.word_idx += 1;
+            self.current = *self.words.get(self.word_idx)?;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1; // clear the lowest set bit
+        Some(self.word_idx * 64 + bit)
+    }
+}
+
+// ////////////// GROWABLE BITSET /////////////////////
+#[derive(Debug, Clone, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(bits: usize) -> Self {
+        BitSet {
+            words: vec![0; bits.div_ceil(64)],
+        }
+    }
+
+    // returns true if the value wasn't in the set before
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, mask) = split(value);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] |= mask;
+        !was_set
+    }
+
+    // returns true if the value was in the set
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, mask) = split(value);
+        match self.words.get_mut(word) {
+            Some(w) => {
+                let was_set = *w & mask != 0;
+                *w &= !mask;
+                was_set
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        let (word, mask) = split(value);
+        self.words.get(word).is_some_and(|w| w & mask != 0)
+    }
+
+    // popcount of the whole set
+    pub fn len(&self) -> usize {
+        count_ones(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    // values in increasing order
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(&self.words)
+    }
+
+    pub fn union_with(&mut self, other: &BitSet) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (w, o) in self.words.iter_mut().zip(other.words.iter()) {
+            *w |= o;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        for (i, w) in self.words.iter_mut().enumerate() {
+            *w &= other.words.get(i).copied().unwrap_or(0);
+        }
+    }
+
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+}
+
+// sets with the same values are equal no matter how much they grew
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        let len = self.words.len().max(other.words.len());
+        (0..len).all(|i| {
+            self.words.get(i).copied().unwrap_or(0)
+                == other.words.get(i).copied().unwrap_or(0)
+        })
+    }
+}
+
+impl Eq for BitSet {}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+// ////////////// FIXED SIZE BITSET /////////////////////
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FixedBitSet<const W: usize> {
+    words: [u64; W],
+}
+
+impl<const W: usize> Default for FixedBitSet<W> {
+    fn default() -> Self {
+        FixedBitSet { words: [0; W] }
+    }
+}
+
+impl<const W: usize> FixedBitSet<W> {
+    pub const CAPACITY: usize = W * 64;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // panics if the value doesn't fit in the set
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(
+            value < Self::CAPACITY,
+            "{} doesn't fit in a FixedBitSet of {} bits",
+            value,
+            Self::CAPACITY
+        );
+        let (word, mask) = split(value);
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] |= mask;
+        !was_set
+    }
+
+    pub fn remove(&mut self, value: usize) -> bool {
+        if value >= Self::CAPACITY {
+            return false;
+        }
+        let (word, mask) = split(value);
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        was_set
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        let (word, mask) = split(value);
+        word < W && self.words[word] & mask != 0
+    }
+
+    pub fn len(&self) -> usize {
+        count_ones(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; W];
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(&self.words)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = *self;
+        for (w, o) in result.words.iter_mut().zip(other.words.iter()) {
+            *w |= o;
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = *self;
+        for (w, o) in result.words.iter_mut().zip(other.words.iter()) {
+            *w &= o;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitset_insert_remove() {
+        let mut set = BitSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(200));
+        assert!(set.contains(3));
+        assert!(set.contains(200));
+        assert!(!set.contains(4));
+        assert!(!set.contains(10_000));
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(200));
+        assert!(!set.remove(200));
+        assert!(!set.remove(10_000));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3]);
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_bitset_set_operations() {
+        let a: BitSet = [1, 64, 65, 300].into_iter().collect();
+        let b: BitSet = [0, 1, 65].into_iter().collect();
+        assert_eq!(
+            a.union(&b).iter().collect::<Vec<_>>(),
+            vec![0, 1, 64, 65, 300]
+        );
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![1, 65]);
+        assert_eq!(b.intersection(&a), a.intersection(&b));
+        // equal sets compare equal even if one of them grew more
+        let mut c = BitSet::with_capacity(1000);
+        c.insert(1);
+        c.insert(65);
+        assert_eq!(c, a.intersection(&b));
+    }
+
+    #[test]
+    fn test_fixed_bitset() {
+        let mut a = FixedBitSet::<2>::new();
+        assert_eq!(FixedBitSet::<2>::CAPACITY, 128);
+        a.insert(0);
+        a.insert(127);
+        let mut b = FixedBitSet::<2>::new();
+        b.insert(127);
+        b.insert(5);
+        assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), vec![0, 5, 127]);
+        assert_eq!(a.intersection(&b).len(), 1);
+        assert!(!a.contains(128));
+        assert!(a.remove(0));
+        let mut only_last = FixedBitSet::<2>::new();
+        only_last.insert(127);
+        assert_eq!(a, only_last);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fixed_bitset_overflow() {
+        FixedBitSet::<1>::new().insert(64);
+    }
+}
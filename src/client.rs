@@ -0,0 +1,355 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Talks to the puzzle site. The base url is configurable so everything can be
+// pointed at a local stub server instead of the real thing
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2025;
+// the session cookie is read from here when it isn't given some other way,
+// the file is in .gitignore
+pub const SESSION_FILE: &str = ".aoc_session";
+const USER_AGENT: &str =
+    "github.com/lulvz/advent_of_code_2025 (aoc command line tool)";
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Io(io::Error),
+    Http { status: u16, body: String },
+    Transport(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie, pass --session, set AOC_SESSION or \
+                 write it to {}",
+                SESSION_FILE
+            ),
+            ClientError::Io(e) => write!(f, "{}", e),
+            ClientError::Http { status, body } => {
+                write!(f, "server answered {}: {}", status, body.trim())
+            }
+            ClientError::Transport(e) => write!(f, "request failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        ClientError::Transport(e.to_string())
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        Client {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    // flags win over the environment (AOC_BASE_URL and AOC_SESSION), which
+    // wins over the session file
+    pub fn configure(
+        base_url: Option<String>,
+        session: Option<String>,
+    ) -> Result<Self, ClientError> {
+        let base_url = base_url
+            .or_else(|| std::env::var("AOC_BASE_URL").ok())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        let session = session
+            .or_else(|| std::env::var("AOC_SESSION").ok())
+            .or_else(|| fs::read_to_string(SESSION_FILE).ok())
+            .filter(|s| !s.trim().is_empty())
+            .ok_or(ClientError::MissingSession)?;
+        Ok(Client::new(&base_url, &session))
+    }
+
+    pub fn day_url(&self, day: u32) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    pub fn fetch_input(&self, day: u32) -> Result<String, ClientError> {
+        let mut response = self
+            .agent
+            .get(format!("{}/input", self.day_url(day)))
+            .header("Cookie", format!("session={}", self.session))
+            .call()?;
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+        if status != 200 {
+            return Err(ClientError::Http { status, body });
+        }
+        Ok(body)
+    }
+
+    // returns the html the site answers with, submit::parse_response makes
+    // sense of it
+    pub fn submit_answer(
+        &self,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<String, ClientError> {
+        let mut response = self
+            .agent
+            .post(format!("{}/answer", self.day_url(day)))
+            .header("Cookie", format!("session={}", self.session))
+            .send_form([
+                ("level", part.to_string()),
+                ("answer", answer.to_string()),
+            ])?;
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+        if status != 200 {
+            return Err(ClientError::Http { status, body });
+        }
+        Ok(body)
+    }
+}
+
+// ////////////// INPUT CACHE /////////////////////
+pub fn input_path(inputs_dir: &Path, day: u32) -> PathBuf {
+    inputs_dir.join(format!("day{:02}.txt", day))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+// Inputs never change, so once a day's input is on disk the site is never
+// asked for it again. An empty file doesn't count as cached. The client is
+// only made on a miss, a cached input doesn't need a session
+pub fn fetch_cached(
+    client: impl FnOnce() -> Result<Client, ClientError>,
+    inputs_dir: &Path,
+    day: u32,
+) -> Result<Fetched, ClientError> {
+    let path = input_path(inputs_dir, day);
+    if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+        return Ok(Fetched::Cached(path));
+    }
+    let input = client()?.fetch_input(day)?;
+    fs::create_dir_all(inputs_dir)?;
+    fs::write(&path, input)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+// ////////////// STUB SERVER /////////////////////
+// A tiny HTTP server that answers every request with the same canned
+// response and remembers what it was asked, so the client can be tested
+// without touching the network
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    #[derive(Debug, Clone)]
+    pub struct Request {
+        pub request_line: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    pub struct StubServer {
+        pub base_url: String,
+        pub requests: Arc<Mutex<Vec<Request>>>,
+    }
+
+    impl StubServer {
+        pub fn start(status: u16, body: &str) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let seen = Arc::clone(&requests);
+            let body = body.to_string();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { break };
+                    let mut reader = BufReader::new(&mut stream);
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).unwrap();
+                    let mut headers = Vec::new();
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        let line = line.trim_end();
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some((n, v)) = line.split_once(':') {
+                            headers.push((n.to_string(), v.trim().to_string()));
+                        }
+                    }
+                    let length: usize = headers
+                        .iter()
+                        .find(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+                        .map(|(_, v)| v.parse().unwrap())
+                        .unwrap_or(0);
+                    let mut request_body = vec![0; length];
+                    reader.read_exact(&mut request_body).unwrap();
+                    seen.lock().unwrap().push(Request {
+                        request_line: request_line.trim_end().to_string(),
+                        headers,
+                        body: String::from_utf8(request_body).unwrap(),
+                    });
+                    let response = format!(
+                        "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\n\
+                         Connection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    stream.write_all(response.as_bytes()).unwrap();
+                }
+            });
+            StubServer { base_url, requests }
+        }
+
+        pub fn requests(&self) -> Vec<Request> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::stub::StubServer;
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "aoc_client_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let server = StubServer::start(200, "1,2,3\n4,5,6\n");
+        let client = Client::new(&server.base_url, "abc123\n");
+        assert_eq!(client.fetch_input(8).unwrap(), "1,2,3\n4,5,6\n");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].request_line, "GET /2025/day/8/input HTTP/1.1");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc123"));
+        assert!(requests[0].header("user-agent").is_some());
+    }
+
+    #[test]
+    fn test_fetch_input_error_status() {
+        let server = StubServer::start(400, "Puzzle inputs differ by user.");
+        let client = Client::new(&server.base_url, "expired");
+        match client.fetch_input(1) {
+            Err(ClientError::Http { status, body }) => {
+                assert_eq!(status, 400);
+                assert_eq!(body, "Puzzle inputs differ by user.");
+            }
+            other => panic!("expected an http error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_submit_answer() {
+        let server = StubServer::start(
+            200,
+            "<article><p>That's not the right answer; your answer is too \
+             low.</p></article>",
+        );
+        let client = Client::new(&server.base_url, "abc123");
+        let body = client.submit_answer(4, 2, "9609").unwrap();
+        assert_eq!(
+            crate::submit::parse_response(&body),
+            crate::submit::Outcome::TooLow
+        );
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].request_line,
+            "POST /2025/day/4/answer HTTP/1.1"
+        );
+        assert_eq!(requests[0].header("cookie"), Some("session=abc123"));
+        assert_eq!(
+            requests[0].header("content-type"),
+            Some("application/x-www-form-urlencoded")
+        );
+        assert_eq!(requests[0].body, "level=2&answer=9609");
+    }
+
+    #[test]
+    fn test_fetch_cached_never_refetches() {
+        let server = StubServer::start(200, "L68\nR48\n");
+        let client = || Ok(Client::new(&server.base_url, "abc123"));
+        let dir = temp_dir("cache");
+        let path = input_path(&dir, 1);
+
+        assert_eq!(
+            fetch_cached(client, &dir, 1).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "L68\nR48\n");
+        assert_eq!(
+            fetch_cached(client, &dir, 1).unwrap(),
+            Fetched::Cached(path.clone())
+        );
+        assert_eq!(server.requests().len(), 1);
+        // no session is needed for a cached input
+        assert_eq!(
+            fetch_cached(|| Err(ClientError::MissingSession), &dir, 1).unwrap(),
+            Fetched::Cached(path.clone())
+        );
+
+        // an empty placeholder file is fetched over
+        fs::write(&path, "").unwrap();
+        assert_eq!(
+            fetch_cached(client, &dir, 1).unwrap(),
+            Fetched::Downloaded(path)
+        );
+        assert_eq!(server.requests().len(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
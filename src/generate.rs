@@ -0,0 +1,744 @@
+// Random but valid puzzle inputs, for testing the solvers on more than the
+// one example and the one real input. Everything is driven by a seeded Rng so
+// a failing input can always be produced again
+#[cfg(any(test, feature = "prop"))]
+use crate::prop::Shrink;
+use std::fmt;
+
+// ////////////// RNG /////////////////////
+// splitmix64, small and good enough for test inputs
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // uniform in lo..=hi
+    pub fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        assert!(lo <= hi, "empty range {}..={}", lo, hi);
+        match (hi - lo).checked_add(1) {
+            Some(span) => lo + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0, len as u64 - 1) as usize
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+}
+
+// ////////////// DAY 01 /////////////////////
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rotation {
+    pub left: bool,
+    pub steps: u64,
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", if self.left { 'L' } else { 'R' }, self.steps)
+    }
+}
+
+#[cfg(any(test, feature = "prop"))]
+impl Shrink for Rotation {
+    fn shrink(&self) -> Vec<Self> {
+        self.steps
+            .shrink()
+            .into_iter()
+            .filter(|&steps| steps > 0)
+            .map(|steps| Rotation { steps, ..*self })
+            .collect()
+    }
+}
+
+pub fn rotations(rng: &mut Rng, count: usize) -> Vec<Rotation> {
+    (0..count)
+        .map(|_| Rotation {
+            left: rng.bool(),
+            steps: rng.range(1, 999),
+        })
+        .collect()
+}
+
+pub fn render_rotations(rotations: &[Rotation]) -> String {
+    rotations.iter().map(|r| format!("{}\n", r)).collect()
+}
+
+// ////////////// DAY 02 /////////////////////
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+#[cfg(any(test, feature = "prop"))]
+impl Shrink for IdRange {
+    // the range only gets narrower, from either side
+    fn shrink(&self) -> Vec<Self> {
+        let width = self.end - self.start;
+        width
+            .shrink()
+            .into_iter()
+            .flat_map(|w| {
+                [
+                    IdRange {
+                        start: self.start,
+                        end: self.start + w,
+                    },
+                    IdRange {
+                        start: self.end - w,
+                        end: self.end,
+                    },
+                ]
+            })
+            .collect()
+    }
+}
+
+// ranges never overlap, like in the puzzle
+pub fn id_ranges(rng: &mut Rng, count: usize, max_width: u64) -> Vec<IdRange> {
+    let mut ranges = Vec::new();
+    let mut start = rng.range(1, 100);
+    for _ in 0..count {
+        let end = start + rng.range(0, max_width);
+        ranges.push(IdRange { start, end });
+        // jumping ahead by a random power of ten gets ids of every length
+        let jump = 10u64.pow(rng.range(1, 5) as u32);
+        start = end + 1 + rng.range(0, jump);
+    }
+    ranges
+}
+
+pub fn render_id_ranges(ranges: &[IdRange]) -> String {
+    let ranges: Vec<String> = ranges
+        .iter()
+        .map(|r| format!("{}-{}", r.start, r.end))
+        .collect();
+    ranges.join(",")
+}
+
+// ////////////// DAY 03 /////////////////////
+pub fn battery_banks(rng: &mut Rng, count: usize, len: usize) -> String {
+    (0..count)
+        .map(|_| {
+            let mut bank: String = (0..len)
+                .map(|_| char::from(b'0' + rng.range(1, 9) as u8))
+                .collect();
+            bank.push('\n');
+            bank
+        })
+        .collect()
+}
+
+// ////////////// DAY 04 /////////////////////
+// about 60% of the cells have a roll, roughly like the real map
+pub fn roll_map(rng: &mut Rng, width: usize, height: usize) -> String {
+    (0..height)
+        .map(|_| {
+            let mut row: String = (0..width)
+                .map(|_| if rng.range(0, 9) < 6 { '@' } else { '.' })
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+// ////////////// DAY 05 /////////////////////
+// the fresh ranges can overlap, and about half of the ids are picked from
+// inside some range so both answers are interesting
+pub fn fresh_ranges_and_ids(
+    rng: &mut Rng,
+    ranges: usize,
+    ids: usize,
+) -> String {
+    const MAX_ID: u64 = 1_000_000_000_000_000;
+    let ranges: Vec<(u64, u64)> = (0..ranges)
+        .map(|_| {
+            let start = rng.range(1, MAX_ID);
+            (start, start + rng.range(0, MAX_ID / 1000))
+        })
+        .collect();
+    let mut input: String = ranges
+        .iter()
+        .map(|(s, e)| format!("{}-{}\n", s, e))
+        .collect();
+    input.push('\n');
+    for _ in 0..ids {
+        let id = if ranges.is_empty() || rng.bool() {
+            rng.range(1, MAX_ID)
+        } else {
+            let &(start, end) = rng.choose(&ranges);
+            rng.range(start, end)
+        };
+        input.push_str(&format!("{}\n", id));
+    }
+    input
+}
+
+// ////////////// DAY 06 /////////////////////
+// Every problem is a column of numbers with the operator under it. The
+// numbers in a column can be aligned to either side, so each one is stored
+// with the spaces in front of it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub multiply: bool,
+    pub numbers: Vec<(usize, u64)>,
+}
+
+impl Problem {
+    fn width(&self) -> usize {
+        self.numbers
+            .iter()
+            .map(|(pad, n)| pad + n.to_string().len())
+            .max()
+            .unwrap_or(1)
+    }
+
+    // A column of spaces is what separates the problems, so every column
+    // inside a problem needs a digit. Read top to bottom the digits of a
+    // column also can't have a gap, the real inputs never do
+    fn is_valid(&self) -> bool {
+        (0..self.width()).all(|col| {
+            let has_digit: Vec<bool> = self
+                .numbers
+                .iter()
+                .map(|&(pad, n)| {
+                    (pad..pad + n.to_string().len()).contains(&col)
+                })
+                .collect();
+            let first = has_digit.iter().position(|&d| d);
+            let last = has_digit.iter().rposition(|&d| d);
+            match (first, last) {
+                (Some(first), Some(last)) => {
+                    has_digit[first..=last].iter().all(|&d| d)
+                }
+                _ => false,
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Worksheet {
+    pub problems: Vec<Problem>,
+}
+
+#[cfg(any(test, feature = "prop"))]
+impl Shrink for Worksheet {
+    // drops whole problems or whole rows, every problem has to keep the same
+    // amount of numbers
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        if self.problems.len() > 1 {
+            for i in 0..self.problems.len() {
+                let mut problems = self.problems.clone();
+                problems.remove(i);
+                candidates.push(Worksheet { problems });
+            }
+        }
+        let rows = self.problems[0].numbers.len();
+        if rows > 1 {
+            for row in 0..rows {
+                let mut problems = self.problems.clone();
+                for p in problems.iter_mut() {
+                    p.numbers.remove(row);
+                }
+                candidates.push(Worksheet { problems });
+            }
+        }
+        candidates.retain(|w| w.problems.iter().all(Problem::is_valid));
+        candidates
+    }
+}
+
+impl fmt::Display for Worksheet {
+    // every line is padded to the full width, the operator line too
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let widths: Vec<usize> =
+            self.problems.iter().map(Problem::width).collect();
+        let rows = self.problems[0].numbers.len();
+        for row in 0..rows {
+            let cells: Vec<String> = self
+                .problems
+                .iter()
+                .zip(&widths)
+                .map(|(p, &w)| {
+                    let (pad, n) = p.numbers[row];
+                    format!("{:<w$}", format!("{}{}", " ".repeat(pad), n))
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        let ops: Vec<String> = self
+            .problems
+            .iter()
+            .zip(&widths)
+            .map(|(p, &w)| {
+                format!("{:<w$}", if p.multiply { '*' } else { '+' })
+            })
+            .collect();
+        writeln!(f, "{}", ops.join(" "))
+    }
+}
+
+// numbers only use the digits 1 to 9, like the puzzle does
+pub fn worksheet(rng: &mut Rng, problems: usize, rows: usize) -> Worksheet {
+    let problems = (0..problems)
+        .map(|_| {
+            let width = rng.range(1, 4) as usize;
+            let align_left = rng.bool();
+            // retried until the columns come out without gaps, with only a
+            // few rows that doesn't take long
+            loop {
+                let numbers = (0..rows)
+                    .map(|_| {
+                        let digits = rng.range(1, width as u64) as usize;
+                        let n = (0..digits)
+                            .fold(0, |n, _| n * 10 + rng.range(1, 9));
+                        let pad = if align_left { 0 } else { width - digits };
+                        (pad, n)
+                    })
+                    .collect();
+                let problem = Problem {
+                    multiply: rng.bool(),
+                    numbers,
+                };
+                if problem.is_valid() {
+                    break problem;
+                }
+            }
+        })
+        .collect();
+    Worksheet { problems }
+}
+
+// ////////////// DAY 07 /////////////////////
+// The beam starts in the middle of the top row and splitters are only on
+// every other row. Like the real input, the splitters on the kth splitter
+// row are inside the triangle the beam can reach and on every other column,
+// so the beam never leaves the grid
+pub fn splitter_grid(rng: &mut Rng, splitter_rows: usize) -> String {
+    let width = 2 * splitter_rows + 1;
+    let start = splitter_rows;
+    let empty = format!("{}\n", ".".repeat(width));
+    let mut grid = format!("{}S{}\n", ".".repeat(start), ".".repeat(start));
+    for k in 1..=splitter_rows {
+        grid.push_str(&empty);
+        let row: String = (0..width)
+            .map(|col| {
+                let reachable =
+                    col.abs_diff(start) < k && (col + k + start) % 2 == 1;
+                if reachable && rng.range(0, 9) < 8 {
+                    '^'
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        grid.push_str(&row);
+        grid.push('\n');
+    }
+    grid.push_str(&empty);
+    grid
+}
+
+// ////////////// DAY 08 /////////////////////
+pub fn junction_boxes(rng: &mut Rng, count: usize) -> String {
+    (0..count)
+        .map(|_| {
+            format!(
+                "{},{},{}\n",
+                rng.range(0, 99_999),
+                rng.range(0, 99_999),
+                rng.range(0, 99_999)
+            )
+        })
+        .collect()
+}
+
+// ////////////// DAY 09 /////////////////////
+// A rectilinear polygon made of vertical strips: strip i goes from x_i to
+// x_i+1 and from bottom_i to top_i. Neighbouring strips overlap vertically so
+// the outline is a single simple loop, walked along the tops left to right
+// and back along the bottoms. Neighbouring tops (and bottoms) are never
+// equal, so no red tile is in the middle of a straight edge
+pub fn red_tiles(rng: &mut Rng, strips: usize) -> String {
+    const MAX: u64 = 99_999;
+    let strips = strips.max(1);
+    let mut xs: Vec<u64> = Vec::new();
+    let mut x = rng.range(0, 1000);
+    for _ in 0..=strips {
+        xs.push(x);
+        x += rng.range(1, (MAX / (strips as u64 + 1)).max(1));
+    }
+
+    let mut tops: Vec<u64> = Vec::new();
+    let mut bottoms: Vec<u64> = Vec::new();
+    for i in 0..strips {
+        let (mut top, mut bottom);
+        loop {
+            top = rng.range(MAX / 2 + 1, MAX);
+            bottom = rng.range(0, MAX / 2 - 1);
+            let differs =
+                i == 0 || (top != tops[i - 1] && bottom != bottoms[i - 1]);
+            if differs {
+                break;
+            }
+        }
+        tops.push(top);
+        bottoms.push(bottom);
+    }
+
+    let mut corners: Vec<(u64, u64)> = vec![(xs[0], tops[0])];
+    for i in 0..strips {
+        corners.push((xs[i + 1], tops[i]));
+        if i + 1 < strips {
+            corners.push((xs[i + 1], tops[i + 1]));
+        }
+    }
+    corners.push((xs[strips], bottoms[strips - 1]));
+    for i in (0..strips).rev() {
+        corners.push((xs[i], bottoms[i]));
+        if i > 0 {
+            corners.push((xs[i], bottoms[i - 1]));
+        }
+    }
+    corners
+        .iter()
+        .map(|(x, y)| format!("{},{}\n", x, y))
+        .collect()
+}
+
+// ////////////// DAY 10 /////////////////////
+// The machine is generated from how many times each button gets pressed, so
+// the lights and joltages it asks for are always reachable
+#[derive(Debug, Clone, PartialEq)]
+pub struct Machine {
+    pub counters: usize,
+    pub buttons: Vec<Vec<usize>>,
+    pub presses: Vec<u64>,
+}
+
+impl Machine {
+    pub fn joltages(&self) -> Vec<u64> {
+        let mut joltages = vec![0; self.counters];
+        for (button, &presses) in self.buttons.iter().zip(&self.presses) {
+            for &counter in button {
+                joltages[counter] += presses;
+            }
+        }
+        joltages
+    }
+
+    pub fn lights(&self) -> Vec<bool> {
+        let mut lights = vec![false; self.counters];
+        for (button, &presses) in self.buttons.iter().zip(&self.presses) {
+            for &counter in button {
+                lights[counter] ^= presses % 2 == 1;
+            }
+        }
+        lights
+    }
+}
+
+impl fmt::Display for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lights: String = self
+            .lights()
+            .iter()
+            .map(|&on| if on { '#' } else { '.' })
+            .collect();
+        write!(f, "[{}]", lights)?;
+        for button in self.buttons.iter() {
+            let counters: Vec<String> =
+                button.iter().map(|c| c.to_string()).collect();
+            write!(f, " ({})", counters.join(","))?;
+        }
+        let joltages: Vec<String> =
+            self.joltages().iter().map(|j| j.to_string()).collect();
+        write!(f, " {{{}}}", joltages.join(","))
+    }
+}
+
+#[cfg(any(test, feature = "prop"))]
+impl Shrink for Machine {
+    // fewer buttons or fewer presses, but something always has to be pressed
+    // or the machine is already done
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        if self.buttons.len() > 1 {
+            for i in 0..self.buttons.len() {
+                let mut machine = self.clone();
+                machine.buttons.remove(i);
+                machine.presses.remove(i);
+                candidates.push(machine);
+            }
+        }
+        for presses in self.presses.shrink() {
+            candidates.push(Machine {
+                presses,
+                ..self.clone()
+            });
+        }
+        candidates.retain(|m| m.presses.iter().any(|&p| p > 0));
+        candidates
+    }
+}
+
+pub fn machine(
+    rng: &mut Rng,
+    counters: usize,
+    buttons: usize,
+    max_presses: u64,
+) -> Machine {
+    let buttons: Vec<Vec<usize>> = (0..buttons)
+        .map(|_| {
+            let mut wiring: Vec<usize> =
+                (0..counters).filter(|_| rng.bool()).collect();
+            if wiring.is_empty() {
+                wiring.push(rng.index(counters));
+            }
+            wiring
+        })
+        .collect();
+    let mut presses: Vec<u64> =
+        buttons.iter().map(|_| rng.range(0, max_presses)).collect();
+    if presses.iter().all(|&p| p == 0) {
+        presses[0] = 1;
+    }
+    Machine {
+        counters,
+        buttons,
+        presses,
+    }
+}
+
+pub fn render_machines(machines: &[Machine]) -> String {
+    machines.iter().map(|m| format!("{}\n", m)).collect()
+}
+
+// ////////////// DAY 11 /////////////////////
+// the n-th name counting aaa, aab, ..., zzz, baaa, ..., so every n gets its
+// own name and there are as many as needed
+fn device_name(mut n: usize) -> String {
+    let mut name: Vec<u8> = Vec::new();
+    while name.len() < 3 || n > 0 {
+        name.push(b'a' + (n % 26) as u8);
+        n /= 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+// The devices are put in a random order and only connect to devices after
+// them, so the graph has no cycles. out is last and is the only device
+// without outputs, svr comes first and you, fft and dac are somewhere in
+// between
+pub fn device_graph(rng: &mut Rng, devices: usize) -> String {
+    const SPECIAL: [&str; 5] = ["svr", "you", "fft", "dac", "out"];
+    let devices = devices.max(SPECIAL.len());
+    let mut names: Vec<String> = (0..)
+        .map(device_name)
+        .filter(|name| !SPECIAL.contains(&name.as_str()))
+        .take(devices - SPECIAL.len())
+        .collect();
+    for special in ["you", "fft", "dac"] {
+        let at = rng.range(0, names.len() as u64) as usize;
+        names.insert(at, special.to_string());
+    }
+    names.insert(0, "svr".to_string());
+    names.push("out".to_string());
+
+    let mut input = String::new();
+    for i in 0..names.len() - 1 {
+        let outputs = rng.range(1, 3).min((names.len() - 1 - i) as u64);
+        let mut targets: Vec<usize> = Vec::new();
+        while targets.len() < outputs as usize {
+            // mostly short hops so paths are long, sometimes a long jump
+            let hop = if rng.range(0, 9) < 8 {
+                rng.range(1, 5)
+            } else {
+                rng.range(1, names.len() as u64)
+            };
+            let target = (i + hop as usize).min(names.len() - 1);
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+        let targets: Vec<&str> =
+            targets.iter().map(|&t| names[t].as_str()).collect();
+        input.push_str(&format!("{}: {}\n", names[i], targets.join(" ")));
+    }
+    input
+}
+
+// ////////////// ANY DAY /////////////////////
+// A whole input for the day, `size` is roughly the amount of lines (or the
+// side of the grid for the grid days). None for days without a generator
+pub fn puzzle_input(day: u32, size: usize, rng: &mut Rng) -> Option<String> {
+    let size = size.max(1);
+    let input = match day {
+        1 => render_rotations(&rotations(rng, size)),
+        2 => render_id_ranges(&id_ranges(rng, size, 100_000)),
+        3 => battery_banks(rng, size, 100),
+        4 => roll_map(rng, size, size),
+        5 => fresh_ranges_and_ids(rng, size, size * 5),
+        6 => worksheet(rng, size, 4).to_string(),
+        7 => splitter_grid(rng, size),
+        8 => junction_boxes(rng, size),
+        9 => red_tiles(rng, size),
+        10 => {
+            let machines: Vec<Machine> = (0..size)
+                .map(|_| {
+                    let counters = rng.range(4, 10) as usize;
+                    // real machines have between 2 fewer and 3 more
+                    // buttons than counters
+                    let buttons = rng
+                        .range(counters as u64 - 2, counters as u64 + 3)
+                        as usize;
+                    machine(rng, counters, buttons, 30)
+                })
+                .collect();
+            render_machines(&machines)
+        }
+        11 => device_graph(rng, size),
+        _ => return None,
+    };
+    Some(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_is_seeded() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..100 {
+            let x = a.range(10, 20);
+            assert!((10..=20).contains(&x));
+            assert_eq!(x, b.range(10, 20));
+        }
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn test_render_worksheet() {
+        let sheet = Worksheet {
+            problems: vec![
+                Problem {
+                    multiply: true,
+                    numbers: vec![(0, 123), (1, 45), (2, 6)],
+                },
+                Problem {
+                    multiply: false,
+                    numbers: vec![(0, 328), (0, 64), (0, 98)],
+                },
+            ],
+        };
+        assert_eq!(sheet.to_string(), "123 328\n 45 64 \n  6 98 \n*   +  \n");
+    }
+
+    #[test]
+    fn test_render_machine() {
+        let machine = Machine {
+            counters: 4,
+            buttons: vec![vec![3], vec![1, 3], vec![0, 2]],
+            presses: vec![1, 2, 3],
+        };
+        assert_eq!(machine.to_string(), "[#.##] (3) (1,3) (0,2) {3,2,3,3}");
+    }
+
+    #[test]
+    fn test_puzzle_input_every_day() {
+        for &day in crate::registry::DAYS {
+            let input = puzzle_input(day, 20, &mut Rng::new(day as u64));
+            assert!(input.is_some_and(|i| !i.is_empty()), "day {}", day);
+            assert_eq!(
+                puzzle_input(day, 20, &mut Rng::new(1)),
+                puzzle_input(day, 20, &mut Rng::new(1))
+            );
+        }
+        assert_eq!(puzzle_input(25, 20, &mut Rng::new(1)), None);
+    }
+
+    #[test]
+    fn test_red_tiles_form_a_loop() {
+        let tiles: Vec<(u64, u64)> = red_tiles(&mut Rng::new(3), 10)
+            .lines()
+            .map(|l| {
+                let (x, y) = l.split_once(',').unwrap();
+                (x.parse().unwrap(), y.parse().unwrap())
+            })
+            .collect();
+        assert_eq!(tiles.len(), 40);
+        for (i, &(x, y)) in tiles.iter().enumerate() {
+            let (nx, ny) = tiles[(i + 1) % tiles.len()];
+            // every edge is horizontal or vertical, and they alternate
+            assert!((x == nx) != (y == ny));
+            assert_eq!(x == nx, i % 2 == 1);
+        }
+    }
+
+    #[test]
+    fn test_device_graph_only_points_forward() {
+        let input = device_graph(&mut Rng::new(5), 50);
+        let names: Vec<&str> = input
+            .lines()
+            .map(|l| l.split_once(':').unwrap().0)
+            .collect();
+        assert_eq!(names.len(), 49);
+        assert_eq!(names[0], "svr");
+        for name in ["you", "fft", "dac"] {
+            assert!(names.contains(&name));
+        }
+        for (i, l) in input.lines().enumerate() {
+            for target in l.split_once(": ").unwrap().1.split(' ') {
+                let later = names.iter().position(|&n| n == target);
+                assert!(target == "out" || later.is_some_and(|j| j > i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_device_graph_more_names_than_three_letters() {
+        assert_eq!(device_name(0), "aaa");
+        assert_eq!(device_name(26 * 26 * 26 - 1), "zzz");
+        assert_eq!(device_name(26 * 26 * 26), "baaa");
+
+        let devices = 26 * 26 * 26 + 100;
+        let input = device_graph(&mut Rng::new(3), devices);
+        let mut names: Vec<&str> = input
+            .lines()
+            .map(|l| l.split_once(':').unwrap().0)
+            .collect();
+        names.sort();
+        names.dedup();
+        // every device but out has a line
+        assert_eq!(names.len(), devices - 1);
+        assert!(names.iter().any(|n| n.len() == 4));
+    }
+}
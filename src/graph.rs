@@ -0,0 +1,361 @@
+// Directed graphs with named nodes, read from lines like `aaa: bbb ccc`, and
+// the dominator analysis on them: which nodes and edges every path between
+// two nodes has to go through
+use crate::intern::Interner;
+use crate::line_grammar;
+use crate::parse::{Cursor, ParseError, delimited, repeated};
+
+// ////////////// CSR /////////////////////
+// Compressed Sparse Row (CSR)
+// the edges would have like 3 5 6 all next to eachother, in a node that has
+// edges to those indexes for example node 0. So edges[0] = 3, edges[1] = 5,
+// edges[2] = 6. And offsets[0] = 0. Then it would follow that offsets[1] = 3
+// because that's the next available spot. And we can determine the amount of
+// outgoing edges 0 has by doing offsets[1] - offsets[0]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjacencyList {
+    pub edges: Vec<usize>, // vector of compacted edges outgoing from every node
+    pub offsets: Vec<usize>, // offsets indexing into the edges vec indicating
+                           // the start of the current node's outgoing edges
+}
+
+impl AdjacencyList {
+    // the edges keep their order within each node
+    pub fn from_edges(nodes: usize, edge_list: &[(usize, usize)]) -> Self {
+        let mut outgoing_edges_count: Vec<usize> = vec![0; nodes];
+        for &edge in edge_list.iter() {
+            outgoing_edges_count[edge.0] += 1;
+        }
+
+        let mut connections = AdjacencyList {
+            edges: vec![0; edge_list.len()],
+            offsets: Vec::with_capacity(nodes + 1),
+        };
+
+        let mut total: usize = 0;
+        for &outgoing_edge_count in outgoing_edges_count.iter() {
+            connections.offsets.push(total);
+            total += outgoing_edge_count;
+        }
+        // last value is used just to get the amount of edges for the last node
+        connections.offsets.push(total);
+
+        let mut current_offsets_in_use = connections.offsets.clone();
+        for &edge in edge_list.iter() {
+            connections.edges[current_offsets_in_use[edge.0]] = edge.1;
+            current_offsets_in_use[edge.0] += 1;
+        }
+        connections
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn outputs(&self, node: usize) -> &[usize] {
+        &self.edges[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    // every (from, to), in the order they're stored
+    pub fn edge_list(&self) -> Vec<(usize, usize)> {
+        (0..self.len())
+            .flat_map(|node| self.outputs(node).iter().map(move |&o| (node, o)))
+            .collect()
+    }
+
+    // the same graph with every edge turned around
+    pub fn reversed(&self) -> Self {
+        let flipped: Vec<(usize, usize)> =
+            self.edge_list().into_iter().map(|(a, b)| (b, a)).collect();
+        AdjacencyList::from_edges(self.len(), &flipped)
+    }
+}
+
+// ////////////// NAMED GRAPHS /////////////////////
+// a node name is anything up to the next space or colon
+fn node_name<'a>(cursor: &mut Cursor<'a>) -> Result<&'a str, ParseError> {
+    let start = cursor.clone();
+    let name = cursor.take_while(|b| !b.is_ascii_whitespace() && b != b':');
+    if name.is_empty() {
+        return Err(start.error("a node name"));
+    }
+    str::from_utf8(name).map_err(|_| start.error("a UTF-8 node name"))
+}
+
+// a whole line like aaa: bbb ccc, the node and the ones it has edges to
+line_grammar! {
+    #[derive(Debug)]
+    struct AdjacencyLine<'a> {
+        node: &'a str = node_name,
+        ":",
+        outputs: Vec<&'a str> = repeated(delimited(" ", node_name, "")),
+    }
+}
+
+// every name gets a dense id, in the order they show up, so they index
+// straight into the CSR
+pub fn parse_named(
+    input: &str,
+) -> Result<(Interner, AdjacencyList), ParseError> {
+    let mut names = Interner::new();
+    let mut edge_list: Vec<(usize, usize)> = Vec::new();
+
+    let mut cursor = Cursor::new(input);
+    while !cursor.is_empty() {
+        let AdjacencyLine { node, outputs } =
+            AdjacencyLine::parse(&mut cursor)?;
+        let from = names.intern(node) as usize;
+        for output in outputs {
+            edge_list.push((from, names.intern(output) as usize));
+        }
+    }
+
+    let graph = AdjacencyList::from_edges(names.len(), &edge_list);
+    Ok((names, graph))
+}
+
+// ////////////// DOMINATORS /////////////////////
+// Node d dominates n when every path from the root to n goes through d. The
+// immediate dominator is the closest one, they make up a tree under the
+// root. This is the iterative version from Cooper, Harvey and Kennedy: with
+// the nodes in reverse postorder every node's dominator is the common
+// ancestor of its inputs' dominators, repeated until nothing changes.
+// The root is its own dominator, nodes the root can't reach have None
+pub fn dominators(graph: &AdjacencyList, root: usize) -> Vec<Option<usize>> {
+    // postorder with an explicit stack, the graphs can be deep
+    let mut order: Vec<usize> = Vec::new();
+    let mut seen = vec![false; graph.len()];
+    let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+    seen[root] = true;
+    while let Some((node, next)) = stack.pop() {
+        match graph.outputs(node).get(next) {
+            Some(&output) => {
+                stack.push((node, next + 1));
+                if !seen[output] {
+                    seen[output] = true;
+                    stack.push((output, 0));
+                }
+            }
+            None => order.push(node),
+        }
+    }
+    order.reverse();
+    let mut rank = vec![usize::MAX; graph.len()];
+    for (i, &node) in order.iter().enumerate() {
+        rank[node] = i;
+    }
+
+    let inputs = graph.reversed();
+    let mut idom: Vec<Option<usize>> = vec![None; graph.len()];
+    idom[root] = Some(root);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &node in order.iter().skip(1) {
+            let mut new_idom: Option<usize> = None;
+            for &input in inputs.outputs(node) {
+                if idom[input].is_none() {
+                    continue;
+                }
+                new_idom = Some(match new_idom {
+                    None => input,
+                    Some(other) => {
+                        // walk both up the tree until they meet
+                        let (mut a, mut b) = (input, other);
+                        while a != b {
+                            while rank[a] > rank[b] {
+                                a = idom[a].unwrap();
+                            }
+                            while rank[b] > rank[a] {
+                                b = idom[b].unwrap();
+                            }
+                        }
+                        a
+                    }
+                });
+            }
+            if new_idom != idom[node] {
+                idom[node] = new_idom;
+                changed = true;
+            }
+        }
+    }
+    idom
+}
+
+// the same on the reversed graph, p post-dominates n when every path from n
+// to the sink goes through p
+pub fn post_dominators(
+    graph: &AdjacencyList,
+    sink: usize,
+) -> Vec<Option<usize>> {
+    dominators(&graph.reversed(), sink)
+}
+
+// What every path from the source to the sink has to go through
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mandatory {
+    pub nodes: Vec<usize>, // in path order, source and sink included
+    pub edges: Vec<(usize, usize)>, // in path order too
+}
+
+// The nodes are the dominators of the sink. For the edges every edge gets a
+// node of its own put in the middle of it, the edge is on every path exactly
+// when that middle node dominates the sink. None if there are no paths
+pub fn mandatory(
+    graph: &AdjacencyList,
+    source: usize,
+    sink: usize,
+) -> Option<Mandatory> {
+    let nodes = graph.len();
+    let edge_list = graph.edge_list();
+    let mut split: Vec<(usize, usize)> =
+        Vec::with_capacity(2 * edge_list.len());
+    for (e, &(from, to)) in edge_list.iter().enumerate() {
+        split.push((from, nodes + e));
+        split.push((nodes + e, to));
+    }
+    let split = AdjacencyList::from_edges(nodes + edge_list.len(), &split);
+    let idom = dominators(&split, source);
+
+    let mut chain = vec![sink];
+    let mut node = sink;
+    while node != source {
+        node = idom[node]?;
+        chain.push(node);
+    }
+    chain.reverse();
+    Some(Mandatory {
+        nodes: chain.iter().copied().filter(|&n| n < nodes).collect(),
+        edges: chain
+            .iter()
+            .filter(|&&n| n >= nodes)
+            .map(|&n| edge_list[n - nodes])
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+    use crate::prop;
+
+    // two ways around from a to c, then a single bridge to d and e
+    const DIAMOND: &str = "a: b x\nb: c\nx: c\nc: d\nd: e\nd2: e\n";
+
+    #[test]
+    fn test_parse_named() {
+        let (names, graph) = parse_named(DIAMOND).unwrap();
+        assert_eq!(graph.len(), 7);
+        assert_eq!(names.name(2), "x");
+        assert_eq!(graph.outputs(0), &[1, 2]);
+        assert_eq!(graph.reversed().outputs(3), &[1, 2]);
+
+        let error = |input| parse_named(input).unwrap_err().to_string();
+        assert_eq!(
+            error("aaa: bbb  ccc"),
+            "line 1, column 10: expected a node name for outputs, found ' '"
+        );
+        assert_eq!(
+            error("a: b\n: bbb"),
+            "line 2, column 1: expected a node name for node, found ':'"
+        );
+        assert_eq!(
+            error("aaa bbb"),
+            "line 1, column 4: expected ':', found ' '"
+        );
+    }
+
+    #[test]
+    fn test_dominators() {
+        let (names, graph) = parse_named(DIAMOND).unwrap();
+        let id = |name| names.get(name).unwrap() as usize;
+        let idom = dominators(&graph, id("a"));
+        assert_eq!(idom[id("a")], Some(id("a")));
+        assert_eq!(idom[id("c")], Some(id("a")));
+        assert_eq!(idom[id("e")], Some(id("d")));
+        assert_eq!(idom[id("d2")], None);
+
+        let ipdom = post_dominators(&graph, id("e"));
+        assert_eq!(ipdom[id("a")], Some(id("c")));
+        assert_eq!(ipdom[id("b")], Some(id("c")));
+        assert_eq!(ipdom[id("d2")], Some(id("e")));
+    }
+
+    #[test]
+    fn test_mandatory() {
+        let (names, graph) = parse_named(DIAMOND).unwrap();
+        let id = |name| names.get(name).unwrap() as usize;
+        let found = mandatory(&graph, id("a"), id("e")).unwrap();
+        assert_eq!(found.nodes, vec![id("a"), id("c"), id("d"), id("e")]);
+        assert_eq!(found.edges, vec![(id("c"), id("d")), (id("d"), id("e"))]);
+        assert_eq!(mandatory(&graph, id("e"), id("a")), None);
+
+        // two parallel edges, so neither of them is needed
+        let (_, doubled) = parse_named("a: b b\n").unwrap();
+        assert_eq!(mandatory(&doubled, 0, 1).unwrap().edges, vec![]);
+    }
+
+    // every path from node to sink, depth first
+    fn all_paths(
+        graph: &AdjacencyList,
+        node: usize,
+        sink: usize,
+    ) -> Vec<Vec<usize>> {
+        if node == sink {
+            return vec![vec![sink]];
+        }
+        let mut paths = Vec::new();
+        for &output in graph.outputs(node) {
+            for mut path in all_paths(graph, output, sink) {
+                path.insert(0, node);
+                paths.push(path);
+            }
+        }
+        paths
+    }
+
+    #[test]
+    fn prop_mandatory_is_on_every_path() {
+        // the graph is seeded by its size, so shrinking the size shrinks the
+        // graph
+        prop::check(
+            50,
+            |rng| rng.range(5, 40) as usize,
+            |&devices| {
+                let input = generate::device_graph(
+                    &mut Rng::new(devices as u64),
+                    devices,
+                );
+                let (names, graph) = parse_named(&input).unwrap();
+                let id = |name| names.get(name).unwrap() as usize;
+                let (svr, out) = (id("svr"), id("out"));
+                let paths = all_paths(&graph, svr, out);
+                let on_every_path =
+                    |node: usize| paths.iter().all(|p| p.contains(&node));
+                let edge_on_every_path = |edge: (usize, usize)| {
+                    paths
+                        .iter()
+                        .all(|p| p.windows(2).any(|e| (e[0], e[1]) == edge))
+                };
+                let found = mandatory(&graph, svr, out).unwrap();
+                let nodes: Vec<usize> = paths[0]
+                    .iter()
+                    .copied()
+                    .filter(|&n| on_every_path(n))
+                    .collect();
+                let edges: Vec<(usize, usize)> = paths[0]
+                    .windows(2)
+                    .map(|e| (e[0], e[1]))
+                    .filter(|&e| edge_on_every_path(e))
+                    .collect();
+                found.nodes == nodes && found.edges == edges
+            },
+        );
+    }
+}
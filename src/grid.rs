@@ -0,0 +1,232 @@
+use crate::utils::{HasX, HasY, Vector, VectorMap};
+
+pub type Position = Vector<i64, 2>;
+
+// x grows to the right and y grows down, same as reading the puzzle input
+const NEIGHBOURS_4: [(i64, i64); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBOURS_8: [(i64, i64); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+// ////////////// SPARSE GRID STRUCT /////////////////////
+// Only the occupied cells are stored, so the grid can be infinite in every
+// direction and mostly empty. The bounding box of the occupied cells is kept
+// up to date on every insert and remove
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: VectorMap<i64, 2, T>,
+    bounds: Option<(Position, Position)>, // inclusive (min, max) corners
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: VectorMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // every character of the map is passed to cell, the ones it returns Some
+    // for are stored. origin is the position of the first character of the
+    // first line
+    pub fn parse(
+        input: &str,
+        origin: Position,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Self {
+        let mut grid = Self::new();
+        for (y, l) in input.lines().enumerate() {
+            for (x, c) in l.chars().enumerate() {
+                if let Some(value) = cell(c) {
+                    let offset = Vector::new2(x as i64, y as i64);
+                    grid.insert(origin + offset, value);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn bounds(&self) -> Option<(Position, Position)> {
+        self.bounds
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn insert(&mut self, pos: Position, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(pos), max.max(pos)),
+            None => (pos, pos),
+        });
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Position) -> Option<T> {
+        let removed = self.cells.remove(&pos);
+        // only a cell on the edge of the bounding box can shrink it
+        if let Some((min, max)) = self.bounds
+            && removed.is_some()
+            && (pos.x() == min.x()
+                || pos.y() == min.y()
+                || pos.x() == max.x()
+                || pos.y() == max.y())
+        {
+            self.recompute_bounds();
+        }
+        removed
+    }
+
+    fn recompute_bounds(&mut self) {
+        self.bounds = None;
+        for &pos in self.cells.keys() {
+            self.bounds = Some(match self.bounds {
+                Some((min, max)) => (min.min(pos), max.max(pos)),
+                None => (pos, pos),
+            });
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    // occupied cells up, left, right and down of pos
+    pub fn neighbours4(
+        &self,
+        pos: Position,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(pos, &NEIGHBOURS_4)
+    }
+
+    // occupied cells around pos, diagonals included
+    pub fn neighbours8(
+        &self,
+        pos: Position,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(pos, &NEIGHBOURS_8)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        pos: Position,
+        offsets: &'static [(i64, i64)],
+    ) -> impl Iterator<Item = (Position, &'a T)> {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let n = pos + Vector::new2(dx, dy);
+            self.cells.get(&n).map(|value| (n, value))
+        })
+    }
+
+    // draws the bounding box of the grid, one line per row, cell gets None
+    // for the empty positions
+    pub fn render(&self, mut cell: impl FnMut(Option<&T>) -> char) -> String {
+        let mut out = String::new();
+        if let Some((min, max)) = self.bounds {
+            for y in *min.y()..=*max.y() {
+                for x in *min.x()..=*max.x() {
+                    out.push(cell(self.get(Vector::new2(x, y))));
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_STRING: &str = "..@@.\n@....\n....@\n";
+
+    fn rolls(origin: Position) -> SparseGrid<char> {
+        SparseGrid::parse(EXAMPLE_STRING, origin, |c| {
+            if c == '@' { Some(c) } else { None }
+        })
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = rolls(Vector::new2(-2, 10));
+        assert_eq!(grid.len(), 4);
+        assert!(grid.contains(Vector::new2(0, 10)));
+        assert!(grid.contains(Vector::new2(-2, 11)));
+        assert_eq!(
+            grid.bounds(),
+            Some((Vector::new2(-2, 10), Vector::new2(2, 12)))
+        );
+        assert_eq!(grid.render(|c| *c.unwrap_or(&'.')), EXAMPLE_STRING);
+    }
+
+    #[test]
+    fn test_bounds_tracking() {
+        let mut grid = rolls(Vector::new2(0, 0));
+        grid.insert(Vector::new2(-5, 1), '@');
+        assert_eq!(
+            grid.bounds(),
+            Some((Vector::new2(-5, 0), Vector::new2(4, 2)))
+        );
+        grid.remove(Vector::new2(-5, 1));
+        grid.remove(Vector::new2(4, 2));
+        assert_eq!(
+            grid.bounds(),
+            Some((Vector::new2(0, 0), Vector::new2(3, 1)))
+        );
+        // removing a cell that isn't there doesn't change anything
+        assert_eq!(grid.remove(Vector::new2(100, 100)), None);
+        for pos in [(2, 0), (3, 0), (0, 1)] {
+            grid.remove(Vector::new2(pos.0, pos.1));
+        }
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render(|_| '#'), "");
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = rolls(Vector::new2(0, 0));
+        let mut n4: Vec<Position> = grid
+            .neighbours4(Vector::new2(2, 1))
+            .map(|(p, _)| p)
+            .collect();
+        n4.sort();
+        assert_eq!(n4, vec![Vector::new2(2, 0)]);
+
+        let mut n8: Vec<Position> = grid
+            .neighbours8(Vector::new2(1, 0))
+            .map(|(p, _)| p)
+            .collect();
+        n8.sort();
+        assert_eq!(n8, vec![Vector::new2(0, 1), Vector::new2(2, 0)]);
+    }
+}
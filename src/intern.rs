@@ -0,0 +1,73 @@
+// Hands out dense ids for names, the first name seen gets 0, the next new one
+// 1 and so on. The ids can index straight into Vecs, which is what the graph
+// days want instead of hashing names everywhere
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    ids: HashMap<String, u32>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // the id of the name, giving it a new one if it wasn't seen before
+    pub fn intern(&mut self, name: &str) -> u32 {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = u32::try_from(self.names.len()).expect("Too many names");
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: u32) -> &str {
+        &self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    // every (id, name) in id order
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (id as u32, name.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interner() {
+        let mut names = Interner::new();
+        assert_eq!(names.intern("svr"), 0);
+        assert_eq!(names.intern("fft"), 1);
+        assert_eq!(names.intern("svr"), 0);
+        assert_eq!(names.intern("κόμβος"), 2);
+        assert_eq!(names.len(), 3);
+        assert_eq!(names.get("fft"), Some(1));
+        assert_eq!(names.get("dac"), None);
+        assert_eq!(names.name(2), "κόμβος");
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            vec![(0, "svr"), (1, "fft"), (2, "κόμβος")]
+        );
+    }
+}
@@ -1,22 +1,112 @@
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, BufRead, BufReader, Read};
 use std::path::Path;
 use std::{
-    fmt::Debug,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+    fmt::{self, Debug, Display},
+    iter::Sum,
+    ops::{
+        Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg,
+        Sub, SubAssign,
+    },
+    str::FromStr,
 };
+
+pub mod bitset;
+// fetching and submitting need the http client, only `aoc` uses them
+#[cfg(feature = "client")]
+pub mod client;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+pub mod intern;
+pub mod parallel;
+pub mod parse;
+// only for tests, the solvers themselves never link it
+#[cfg(any(test, feature = "prop"))]
+pub mod prop;
+pub mod registry;
+pub mod scaffold;
+pub mod search;
+#[cfg(feature = "client")]
+pub mod submit;
+
+// Pulls in the tests build.rs generated from examples/dayNN for the binary
+// it's used in, goes inside the day's tests module
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        include!(concat!(
+            env!("OUT_DIR"),
+            "/examples/",
+            env!("CARGO_BIN_NAME"),
+            ".rs"
+        ));
+    };
+}
+
 pub mod utils {
     use std::array;
 
     use super::*;
 
-    pub fn read_input(filename: &str) -> io::Result<String> {
+    pub fn open_input(filename: &str) -> io::Result<BufReader<File>> {
         let path = Path::new("./inputs/").join(filename);
-        let mut file = File::open(path)?;
+        Ok(BufReader::new(File::open(path)?))
+    }
+
+    // the whole file at once, for the days that need all of it anyway
+    pub fn read_input(filename: &str) -> io::Result<String> {
         let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
+        open_input(filename)?.read_to_string(&mut contents)?;
+
+        Ok(contents)
+    }
 
-        Ok(contents.to_string())
+    // what a day prints when its input can't be read, however it read it
+    pub fn report_input_error(filename: &str, e: &io::Error) {
+        eprintln!("Error reading input file: {}", e);
+        eprintln!("Make sure 'inputs/{}' exists.", filename);
+    }
+
+    // ////////////// STREAMING INPUT /////////////////////
+    // Parses one record per line out of any reader. The same line buffer is
+    // reused for every line, so memory stays the same however big the input
+    // is. Lines come out like str::lines gives them, without the \n or \r\n
+    pub struct Records<R, F> {
+        reader: R,
+        line: String,
+        parse: F,
+    }
+
+    impl<R: BufRead, T, F: FnMut(&str) -> T> Iterator for Records<R, F> {
+        type Item = io::Result<T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => None,
+                Ok(_) => {
+                    let line =
+                        self.line.strip_suffix('\n').unwrap_or(&self.line);
+                    let line = line.strip_suffix('\r').unwrap_or(line);
+                    Some(Ok((self.parse)(line)))
+                }
+                Err(e) => Some(Err(e)),
+            }
+        }
+    }
+
+    pub fn records<R, T, F>(reader: R, parse: F) -> Records<R, F>
+    where
+        R: BufRead,
+        F: FnMut(&str) -> T,
+    {
+        Records {
+            reader,
+            line: String::new(),
+            parse,
+        }
     }
 
     pub trait Scalar:
@@ -55,18 +145,283 @@ pub mod utils {
     {
     }
 
+    // absolute difference between two scalars that never underflows, for the
+    // unsigned types it's the same as abs_diff, for the floats it's |a - b|.
+    // The signed types go through abs_diff too, which can't overflow on the
+    // way, but the distance itself has to fit: i8::MIN to 0 is 128 and
+    // panics, in release builds as well
+    pub trait ScalarDistance: Scalar {
+        fn distance(self, rhs: Self) -> Self;
+    }
+
+    macro_rules! impl_scalar_distance_unsigned {
+        ($($t:ty),*) => {
+            $(impl ScalarDistance for $t {
+                fn distance(self, rhs: Self) -> Self {
+                    self.abs_diff(rhs)
+                }
+            })*
+        };
+    }
+
+    macro_rules! impl_scalar_distance_signed {
+        ($($t:ty),*) => {
+            $(impl ScalarDistance for $t {
+                fn distance(self, rhs: Self) -> Self {
+                    Self::try_from(self.abs_diff(rhs))
+                        .expect("Distance doesn't fit the signed type")
+                }
+            })*
+        };
+    }
+
+    macro_rules! impl_scalar_distance_float {
+        ($($t:ty),*) => {
+            $(impl ScalarDistance for $t {
+                fn distance(self, rhs: Self) -> Self {
+                    (self - rhs).abs()
+                }
+            })*
+        };
+    }
+
+    impl_scalar_distance_unsigned!(u8, u16, u32, u64, u128, usize);
+    impl_scalar_distance_signed!(i8, i16, i32, i64, i128, isize);
+    impl_scalar_distance_float!(f32, f64);
+
+    // integer scalars with explicit overflow behaviour, so code can choose
+    // between getting None, clamping or wrapping around instead of panicking
+    // in debug builds
+    pub trait IntScalar: Scalar + Ord {
+        fn checked_add(self, rhs: Self) -> Option<Self>;
+        fn checked_sub(self, rhs: Self) -> Option<Self>;
+        fn checked_mul(self, rhs: Self) -> Option<Self>;
+        fn checked_div(self, rhs: Self) -> Option<Self>;
+        fn saturating_add(self, rhs: Self) -> Self;
+        fn saturating_sub(self, rhs: Self) -> Self;
+        fn saturating_mul(self, rhs: Self) -> Self;
+        fn wrapping_add(self, rhs: Self) -> Self;
+        fn wrapping_sub(self, rhs: Self) -> Self;
+        fn wrapping_mul(self, rhs: Self) -> Self;
+    }
+
+    // the trait methods have the same names as the inherent ones, inherent
+    // methods always win in method resolution so these just forward to them
+    macro_rules! impl_int_scalar {
+        ($($t:ty),*) => {
+            $(impl IntScalar for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    self.checked_add(rhs)
+                }
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    self.checked_sub(rhs)
+                }
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    self.checked_mul(rhs)
+                }
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    self.checked_div(rhs)
+                }
+                fn saturating_add(self, rhs: Self) -> Self {
+                    self.saturating_add(rhs)
+                }
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    self.saturating_sub(rhs)
+                }
+                fn saturating_mul(self, rhs: Self) -> Self {
+                    self.saturating_mul(rhs)
+                }
+                fn wrapping_add(self, rhs: Self) -> Self {
+                    self.wrapping_add(rhs)
+                }
+                fn wrapping_sub(self, rhs: Self) -> Self {
+                    self.wrapping_sub(rhs)
+                }
+                fn wrapping_mul(self, rhs: Self) -> Self {
+                    self.wrapping_mul(rhs)
+                }
+            })*
+        };
+    }
+
+    impl_int_scalar!(
+        u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+    );
+
     // ////////////// VECTOR STRUCT /////////////////////
-    #[derive(Debug, Copy, Clone)]
+    // comparisons are lexicographic (x first, then y, ...), and the derives
+    // only apply when the scalar supports them, so Vector<f64, N> is not Eq
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
     pub struct Vector<T: Scalar, const N: usize> {
         data: [T; N],
     }
 
+    // for sparse grids keyed by position
+    pub type VectorMap<T, const N: usize, V> = HashMap<Vector<T, N>, V>;
+    pub type VectorSet<T, const N: usize> = HashSet<Vector<T, N>>;
+
     impl<T: Scalar, const N: usize> From<[T; N]> for Vector<T, N> {
         fn from(data: [T; N]) -> Self {
             Vector { data }
         }
     }
 
+    // ////////////// PARSING AND PRINTING /////////////////////
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ParseVectorError {
+        UnbalancedParens,
+        WrongArity { expected: usize, found: usize },
+        InvalidScalar(String),
+    }
+
+    impl Display for ParseVectorError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                ParseVectorError::UnbalancedParens => {
+                    write!(f, "unbalanced parentheses around vector")
+                }
+                ParseVectorError::WrongArity { expected, found } => write!(
+                    f,
+                    "expected {} components, found {}",
+                    expected, found
+                ),
+                ParseVectorError::InvalidScalar(s) => {
+                    write!(f, "invalid vector component {:?}", s)
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for ParseVectorError {}
+
+    // accepts both "1,2,3" and "(1, 2, 3)", the amount of components has to
+    // be exactly N
+    impl<T: Scalar + FromStr, const N: usize> FromStr for Vector<T, N> {
+        type Err = ParseVectorError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let s = s.trim();
+            let s = match (s.strip_prefix('('), s.ends_with(')')) {
+                (Some(inner), true) => &inner[..inner.len() - 1],
+                (None, false) => s,
+                _ => return Err(ParseVectorError::UnbalancedParens),
+            };
+
+            let found = s.split(',').count();
+            if found != N {
+                return Err(ParseVectorError::WrongArity {
+                    expected: N,
+                    found,
+                });
+            }
+
+            let mut data = [T::default(); N];
+            for (d, part) in data.iter_mut().zip(s.split(',')) {
+                *d = part.trim().parse().map_err(|_| {
+                    ParseVectorError::InvalidScalar(part.trim().to_string())
+                })?;
+            }
+            Ok(data.into())
+        }
+    }
+
+    impl<T: Scalar + Display, const N: usize> Display for Vector<T, N> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "(")?;
+            for (i, d) in self.data.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", d)?;
+            }
+            write!(f, ")")
+        }
+    }
+
+    // vectors are (de)serialized as a sequence of exactly N scalars, so in
+    // JSON they look like [1, 2, 3]
+    #[cfg(feature = "serde")]
+    impl<T: Scalar + serde::Serialize, const N: usize> serde::Serialize
+        for Vector<T, N>
+    {
+        fn serialize<S: serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeTuple;
+            let mut tuple = serializer.serialize_tuple(N)?;
+            for d in self.data.iter() {
+                tuple.serialize_element(d)?;
+            }
+            tuple.end()
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de, T: Scalar + serde::Deserialize<'de>, const N: usize>
+        serde::Deserialize<'de> for Vector<T, N>
+    {
+        fn deserialize<D: serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            struct VectorVisitor<T, const N: usize>(
+                std::marker::PhantomData<T>,
+            );
+
+            impl<'de, T: Scalar + serde::Deserialize<'de>, const N: usize>
+                serde::de::Visitor<'de> for VectorVisitor<T, N>
+            {
+                type Value = Vector<T, N>;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    write!(f, "a sequence of {} scalars", N)
+                }
+
+                fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                    self,
+                    mut seq: A,
+                ) -> Result<Self::Value, A::Error> {
+                    let mut data = [T::default(); N];
+                    for (i, d) in data.iter_mut().enumerate() {
+                        *d = seq.next_element()?.ok_or_else(|| {
+                            serde::de::Error::invalid_length(i, &self)
+                        })?;
+                    }
+                    if seq.next_element::<T>()?.is_some() {
+                        return Err(serde::de::Error::invalid_length(
+                            N + 1,
+                            &self,
+                        ));
+                    }
+                    Ok(data.into())
+                }
+            }
+
+            deserializer
+                .deserialize_tuple(N, VectorVisitor(std::marker::PhantomData))
+        }
+    }
+
+    impl<T: Scalar, const N: usize> Default for Vector<T, N> {
+        fn default() -> Self {
+            [T::default(); N].into()
+        }
+    }
+
+    impl<T: Scalar, const N: usize> Index<usize> for Vector<T, N> {
+        type Output = T;
+
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.data[index]
+        }
+    }
+
+    impl<T: Scalar, const N: usize> IndexMut<usize> for Vector<T, N> {
+        fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+            &mut self.data[index]
+        }
+    }
+
     // ////////////// OPERATOR IMPLEMENTATIONS /////////////////////
     impl<T: Scalar, const N: usize> Add for Vector<T, N> {
         type Output = Self;
@@ -131,12 +486,64 @@ pub mod utils {
             }
         }
     }
+
+    impl<T: Scalar + Neg<Output = T>, const N: usize> Neg for Vector<T, N> {
+        type Output = Self;
+
+        fn neg(self) -> Self::Output {
+            array::from_fn(|i| -self.data[i]).into()
+        }
+    }
+
+    impl<T: Scalar, const N: usize> Sum for Vector<T, N> {
+        fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+            iter.fold(Self::default(), |acc, v| acc + v)
+        }
+    }
+
+    // ////////////// SCALAR OPERATOR IMPLEMENTATIONS /////////////////////
+    impl<T: Scalar, const N: usize> Mul<T> for Vector<T, N> {
+        type Output = Self;
+
+        fn mul(self, rhs: T) -> Self::Output {
+            array::from_fn(|i| self.data[i] * rhs).into()
+        }
+    }
+
+    impl<T: Scalar, const N: usize> MulAssign<T> for Vector<T, N> {
+        fn mul_assign(&mut self, rhs: T) {
+            for i in 0..N {
+                self.data[i] *= rhs;
+            }
+        }
+    }
+
+    impl<T: Scalar, const N: usize> Div<T> for Vector<T, N> {
+        type Output = Self;
+
+        fn div(self, rhs: T) -> Self::Output {
+            array::from_fn(|i| self.data[i] / rhs).into()
+        }
+    }
+
+    impl<T: Scalar, const N: usize> DivAssign<T> for Vector<T, N> {
+        fn div_assign(&mut self, rhs: T) {
+            for i in 0..N {
+                self.data[i] /= rhs;
+            }
+        }
+    }
+
     // ////////////// CUSTOM OPERATOR TRAITS /////////////////////
     pub trait Dot {
         type Scalar;
         fn dot(self, rhs: Self) -> Self::Scalar;
     }
 
+    pub trait Cross {
+        fn cross(self, rhs: Self) -> Self;
+    }
+
     // ////////////// CUSTOM OPERATOR IMPLEMENTATIONS /////////////////////
     impl<T: Scalar, const N: usize> Dot for Vector<T, N> {
         type Scalar = T;
@@ -150,6 +557,137 @@ pub mod utils {
         }
     }
 
+    impl<T: Scalar> Cross for Vector<T, 3> {
+        fn cross(self, rhs: Self) -> Self {
+            let [a1, a2, a3] = self.data;
+            let [b1, b2, b3] = rhs.data;
+            [a2 * b3 - a3 * b2, a3 * b1 - a1 * b3, a1 * b2 - a2 * b1].into()
+        }
+    }
+
+    // ////////////// COMPONENT-WISE AND DISTANCE METHODS /////////////////////
+    // min and max are component-wise, these shadow Ord::min and Ord::max
+    // which would pick the lexicographically smaller or larger vector
+    impl<T: Scalar + PartialOrd, const N: usize> Vector<T, N> {
+        pub fn min(self, rhs: Self) -> Self {
+            array::from_fn(|i| {
+                if rhs.data[i] < self.data[i] {
+                    rhs.data[i]
+                } else {
+                    self.data[i]
+                }
+            })
+            .into()
+        }
+
+        pub fn max(self, rhs: Self) -> Self {
+            array::from_fn(|i| {
+                if rhs.data[i] > self.data[i] {
+                    rhs.data[i]
+                } else {
+                    self.data[i]
+                }
+            })
+            .into()
+        }
+    }
+
+    impl<T: ScalarDistance, const N: usize> Vector<T, N> {
+        // component-wise |self - rhs|, works for unsigned scalars too
+        pub fn abs_diff(self, rhs: Self) -> Self {
+            array::from_fn(|i| self.data[i].distance(rhs.data[i])).into()
+        }
+
+        pub fn manhattan_distance(self, rhs: Self) -> T {
+            let mut acc = T::default();
+            for d in self.abs_diff(rhs).data {
+                acc += d;
+            }
+            acc
+        }
+
+        pub fn chebyshev_distance(self, rhs: Self) -> T
+        where
+            T: PartialOrd,
+        {
+            let mut acc = T::default();
+            for d in self.abs_diff(rhs).data {
+                if d > acc {
+                    acc = d;
+                }
+            }
+            acc
+        }
+
+        pub fn distance_squared(self, rhs: Self) -> T {
+            let d = self.abs_diff(rhs);
+            d.dot(d)
+        }
+    }
+
+    // ////////////// CHECKED, SATURATING AND WRAPPING METHODS /////////////////////
+    macro_rules! impl_vector_checked {
+        ($($name:ident),*) => {
+            $(pub fn $name(self, rhs: Self) -> Option<Self> {
+                let mut data = self.data;
+                for i in 0..N {
+                    data[i] = IntScalar::$name(self.data[i], rhs.data[i])?;
+                }
+                Some(data.into())
+            })*
+        };
+    }
+
+    macro_rules! impl_vector_overflowing {
+        ($($name:ident),*) => {
+            $(pub fn $name(self, rhs: Self) -> Self {
+                array::from_fn(|i| {
+                    IntScalar::$name(self.data[i], rhs.data[i])
+                })
+                .into()
+            })*
+        };
+    }
+
+    impl<T: IntScalar, const N: usize> Vector<T, N> {
+        impl_vector_checked!(
+            checked_add,
+            checked_sub,
+            checked_mul,
+            checked_div
+        );
+        impl_vector_overflowing!(
+            saturating_add,
+            saturating_sub,
+            saturating_mul,
+            wrapping_add,
+            wrapping_sub,
+            wrapping_mul
+        );
+    }
+
+    impl<T: Scalar, const N: usize> Vector<T, N> {
+        pub fn length_squared(self) -> T {
+            self.dot(self)
+        }
+    }
+
+    macro_rules! impl_float_length {
+        ($($t:ty),*) => {
+            $(impl<const N: usize> Vector<$t, N> {
+                pub fn length(self) -> $t {
+                    self.length_squared().sqrt()
+                }
+
+                pub fn distance(self, rhs: Self) -> $t {
+                    (self - rhs).length()
+                }
+            })*
+        };
+    }
+
+    impl_float_length!(f32, f64);
+
     // ////////////// COORDINATE NAME TRAITS /////////////////////
     pub trait HasX {
         type Scalar;
@@ -169,6 +707,12 @@ pub mod utils {
         fn z_mut(&mut self) -> &mut Self::Scalar;
     }
 
+    pub trait HasW {
+        type Scalar;
+        fn w(&self) -> &Self::Scalar;
+        fn w_mut(&mut self) -> &mut Self::Scalar;
+    }
+
     // ////////////// COORDINATE IMPLEMENTATIONS /////////////////////
     impl<T: Scalar> HasX for Vector<T, 2> {
         type Scalar = T;
@@ -219,4 +763,510 @@ pub mod utils {
             &mut self.data[2]
         }
     }
+
+    impl<T: Scalar> HasX for Vector<T, 4> {
+        type Scalar = T;
+        fn x(&self) -> &Self::Scalar {
+            &self.data[0]
+        }
+        fn x_mut(&mut self) -> &mut Self::Scalar {
+            &mut self.data[0]
+        }
+    }
+
+    impl<T: Scalar> HasY for Vector<T, 4> {
+        type Scalar = T;
+        fn y(&self) -> &Self::Scalar {
+            &self.data[1]
+        }
+        fn y_mut(&mut self) -> &mut Self::Scalar {
+            &mut self.data[1]
+        }
+    }
+
+    impl<T: Scalar> HasZ for Vector<T, 4> {
+        type Scalar = T;
+        fn z(&self) -> &Self::Scalar {
+            &self.data[2]
+        }
+        fn z_mut(&mut self) -> &mut Self::Scalar {
+            &mut self.data[2]
+        }
+    }
+
+    impl<T: Scalar> HasW for Vector<T, 4> {
+        type Scalar = T;
+        fn w(&self) -> &Self::Scalar {
+            &self.data[3]
+        }
+        fn w_mut(&mut self) -> &mut Self::Scalar {
+            &mut self.data[3]
+        }
+    }
+
+    // ////////////// CONSTRUCTORS AND SWIZZLES /////////////////////
+    impl<T: Scalar, const N: usize> Vector<T, N> {
+        // builds a vector out of the components at the given indices, like
+        // v.swizzle([2, 0]) for (z, x)
+        pub fn swizzle<const M: usize>(
+            self,
+            indices: [usize; M],
+        ) -> Vector<T, M> {
+            indices.map(|i| self.data[i]).into()
+        }
+    }
+
+    impl<T: Scalar> Vector<T, 2> {
+        pub fn new2(x: T, y: T) -> Self {
+            [x, y].into()
+        }
+
+        pub fn yx(self) -> Vector<T, 2> {
+            self.swizzle([1, 0])
+        }
+    }
+
+    impl<T: Scalar> Vector<T, 3> {
+        pub fn new3(x: T, y: T, z: T) -> Self {
+            [x, y, z].into()
+        }
+
+        pub fn xy(self) -> Vector<T, 2> {
+            self.swizzle([0, 1])
+        }
+
+        pub fn xz(self) -> Vector<T, 2> {
+            self.swizzle([0, 2])
+        }
+
+        pub fn yz(self) -> Vector<T, 2> {
+            self.swizzle([1, 2])
+        }
+
+        pub fn yx(self) -> Vector<T, 2> {
+            self.swizzle([1, 0])
+        }
+    }
+
+    impl<T: Scalar> Vector<T, 4> {
+        pub fn new4(x: T, y: T, z: T, w: T) -> Self {
+            [x, y, z, w].into()
+        }
+
+        pub fn xy(self) -> Vector<T, 2> {
+            self.swizzle([0, 1])
+        }
+
+        pub fn xz(self) -> Vector<T, 2> {
+            self.swizzle([0, 2])
+        }
+
+        pub fn yz(self) -> Vector<T, 2> {
+            self.swizzle([1, 2])
+        }
+
+        pub fn yx(self) -> Vector<T, 2> {
+            self.swizzle([1, 0])
+        }
+
+        pub fn xyz(self) -> Vector<T, 3> {
+            self.swizzle([0, 1, 2])
+        }
+    }
+
+    // ////////////// MATRIX STRUCT /////////////////////
+    // R rows and C columns, stored row by row, so a Matrix<T, R, C> times a
+    // Vector<T, C> gives a Vector<T, R>
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct Matrix<T: Scalar, const R: usize, const C: usize> {
+        data: [[T; C]; R],
+    }
+
+    impl<T: Scalar, const R: usize, const C: usize> From<[[T; C]; R]>
+        for Matrix<T, R, C>
+    {
+        fn from(data: [[T; C]; R]) -> Self {
+            Matrix { data }
+        }
+    }
+
+    impl<T: Scalar, const R: usize, const C: usize> Default for Matrix<T, R, C> {
+        fn default() -> Self {
+            [[T::default(); C]; R].into()
+        }
+    }
+
+    impl<T: Scalar, const R: usize, const C: usize> Index<(usize, usize)>
+        for Matrix<T, R, C>
+    {
+        type Output = T;
+
+        fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+            &self.data[i][j]
+        }
+    }
+
+    impl<T: Scalar, const R: usize, const C: usize> IndexMut<(usize, usize)>
+        for Matrix<T, R, C>
+    {
+        fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+            &mut self.data[i][j]
+        }
+    }
+
+    impl<T: Scalar, const R: usize, const C: usize> Matrix<T, R, C> {
+        pub fn row(&self, i: usize) -> Vector<T, C> {
+            self.data[i].into()
+        }
+
+        pub fn col(&self, j: usize) -> Vector<T, R> {
+            array::from_fn(|i| self.data[i][j]).into()
+        }
+
+        pub fn transpose(&self) -> Matrix<T, C, R> {
+            array::from_fn(|j| array::from_fn(|i| self.data[i][j])).into()
+        }
+    }
+
+    // T::from(true) is the one, From<bool> is implemented by every integer
+    // and float, where From<u8> leaves out i8
+    impl<T: Scalar + From<bool>, const N: usize> Matrix<T, N, N> {
+        pub fn identity() -> Self {
+            array::from_fn(|i| {
+                array::from_fn(|j| {
+                    if i == j { T::from(true) } else { T::default() }
+                })
+            })
+            .into()
+        }
+    }
+
+    impl<T: Scalar + From<bool> + Neg<Output = T>> Matrix<T, 2, 2> {
+        // rotations for grids where x grows to the right and y grows down,
+        // like the puzzle maps, so clockwise is what you see on the screen
+        pub fn rotation_90_cw() -> Self {
+            let (zero, one) = (T::default(), T::from(true));
+            [[zero, -one], [one, zero]].into()
+        }
+
+        pub fn rotation_90_ccw() -> Self {
+            Self::rotation_90_cw().transpose()
+        }
+    }
+
+    impl<T, const N: usize> Matrix<T, N, N>
+    where
+        T: Scalar + PartialEq + Neg<Output = T> + From<bool>,
+    {
+        pub fn determinant(&self) -> T {
+            // Bareiss algorithm, a gaussian elimination where every division
+            // is exact, so it gives the right answer for integer matrices too.
+            // A 0x0 matrix has the empty product, one, like any identity
+            if N == 0 {
+                return T::from(true);
+            }
+            let zero = T::default();
+            let mut m = self.data;
+            let mut negate = false;
+            let mut last_pivot: Option<T> = None;
+            for k in 0..N - 1 {
+                if m[k][k] == zero {
+                    match (k + 1..N).find(|&i| m[i][k] != zero) {
+                        Some(i) => {
+                            m.swap(i, k);
+                            negate = !negate;
+                        }
+                        None => return zero,
+                    }
+                }
+                for i in k + 1..N {
+                    for j in k + 1..N {
+                        let value = m[i][j] * m[k][k] - m[i][k] * m[k][j];
+                        m[i][j] = match last_pivot {
+                            Some(p) => value / p,
+                            None => value,
+                        };
+                    }
+                }
+                last_pivot = Some(m[k][k]);
+            }
+            if negate {
+                -m[N - 1][N - 1]
+            } else {
+                m[N - 1][N - 1]
+            }
+        }
+    }
+
+    // ////////////// MATRIX OPERATOR IMPLEMENTATIONS /////////////////////
+    impl<T: Scalar, const R: usize, const C: usize> Mul<Vector<T, C>>
+        for Matrix<T, R, C>
+    {
+        type Output = Vector<T, R>;
+
+        fn mul(self, rhs: Vector<T, C>) -> Self::Output {
+            array::from_fn(|i| self.row(i).dot(rhs)).into()
+        }
+    }
+
+    impl<T: Scalar, const R: usize, const C: usize, const K: usize>
+        Mul<Matrix<T, C, K>> for Matrix<T, R, C>
+    {
+        type Output = Matrix<T, R, K>;
+
+        fn mul(self, rhs: Matrix<T, C, K>) -> Self::Output {
+            array::from_fn(|i| array::from_fn(|j| self.row(i).dot(rhs.col(j))))
+                .into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::utils::*;
+
+    #[test]
+    fn test_records() {
+        let input = "1,2\r\n3,4\n\n5,6";
+        // a tiny buffer so the lines get split across reads
+        let reader = std::io::BufReader::with_capacity(2, input.as_bytes());
+        let parsed: Vec<Option<Vector<i64, 2>>> =
+            records(reader, |l| l.parse().ok())
+                .collect::<std::io::Result<_>>()
+                .unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Some([1, 2].into()),
+                Some([3, 4].into()),
+                None,
+                Some([5, 6].into())
+            ]
+        );
+        assert_eq!(
+            records(input.as_bytes(), |l| l.len()).count(),
+            input.lines().count()
+        );
+    }
+
+    #[test]
+    fn test_vector_scalar_ops() {
+        let v: Vector<i64, 3> = [1, -2, 3].into();
+        assert_eq!((v * 2)[1], -4);
+        assert_eq!((v / 2)[2], 1);
+        let mut w = v;
+        w *= 3;
+        w /= 3;
+        assert_eq!(w[0], 1);
+        assert_eq!((-v)[1], 2);
+    }
+
+    #[test]
+    fn test_vector_distances() {
+        let a: Vector<u64, 3> = [1, 10, 3].into();
+        let b: Vector<u64, 3> = [4, 6, 3].into();
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(b.manhattan_distance(a), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!(a.distance_squared(b), 25);
+
+        let c: Vector<f64, 2> = [0.0, 0.0].into();
+        let d: Vector<f64, 2> = [3.0, -4.0].into();
+        assert_eq!(d.length(), 5.0);
+        assert_eq!(c.distance(d), 5.0);
+
+        // far apart signed values, |a - b| would overflow before abs
+        let e: Vector<i8, 2> = [i8::MIN, 100].into();
+        let f: Vector<i8, 2> = [-1, -27].into();
+        let diff = e.abs_diff(f);
+        assert_eq!((diff[0], diff[1]), (127, 127));
+    }
+
+    #[test]
+    #[should_panic(expected = "Distance doesn't fit the signed type")]
+    fn test_signed_distance_too_far() {
+        i8::MIN.distance(0);
+    }
+
+    #[test]
+    fn test_vector_checked_arithmetic() {
+        let a: Vector<u64, 2> = [1, u64::MAX].into();
+        let b: Vector<u64, 2> = [2, 1].into();
+        assert!(a.checked_sub(b).is_none());
+        assert!(a.checked_add(b).is_none());
+        let c = b.checked_sub([1, 1].into()).unwrap();
+        assert_eq!((c[0], c[1]), (1, 0));
+        assert!(b.checked_div([1, 0].into()).is_none());
+
+        let s = a.saturating_sub(b);
+        assert_eq!((s[0], s[1]), (0, u64::MAX - 1));
+        let s = a.saturating_add(b);
+        assert_eq!((s[0], s[1]), (3, u64::MAX));
+
+        let w = a.wrapping_add(b);
+        assert_eq!((w[0], w[1]), (3, 0));
+        let w = a.wrapping_sub(b);
+        assert_eq!((w[0], w[1]), (u64::MAX, u64::MAX - 1));
+
+        let i: Vector<i8, 2> = [i8::MIN, 100].into();
+        let m = i.saturating_mul([2, 2].into());
+        assert_eq!((m[0], m[1]), (i8::MIN, i8::MAX));
+    }
+
+    #[test]
+    fn test_vector_parse_and_display() {
+        let v: Vector<i64, 3> = "1,-2,3".parse().unwrap();
+        assert_eq!((v[0], v[1], v[2]), (1, -2, 3));
+        let v: Vector<i64, 3> = " (4, 5,6) ".parse().unwrap();
+        assert_eq!((v[0], v[1], v[2]), (4, 5, 6));
+        assert_eq!(v.to_string(), "(4, 5, 6)");
+        assert_eq!(v.to_string().parse::<Vector<i64, 3>>().unwrap()[2], 6);
+
+        assert_eq!(
+            "1,2".parse::<Vector<i64, 3>>().unwrap_err(),
+            ParseVectorError::WrongArity {
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            "1,2,3,4".parse::<Vector<i64, 3>>().unwrap_err(),
+            ParseVectorError::WrongArity {
+                expected: 3,
+                found: 4
+            }
+        );
+        assert_eq!(
+            "(1,2,3".parse::<Vector<i64, 3>>().unwrap_err(),
+            ParseVectorError::UnbalancedParens
+        );
+        assert_eq!(
+            "1,x,3".parse::<Vector<i64, 3>>().unwrap_err(),
+            ParseVectorError::InvalidScalar("x".to_string())
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_vector_serde() {
+        let v: Vector<u64, 3> = [162, 817, 812].into();
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(json, "[162,817,812]");
+        let back: Vector<u64, 3> = serde_json::from_str(&json).unwrap();
+        assert_eq!((back[0], back[1], back[2]), (162, 817, 812));
+        assert!(serde_json::from_str::<Vector<u64, 3>>("[1,2]").is_err());
+        assert!(serde_json::from_str::<Vector<u64, 3>>("[1,2,3,4]").is_err());
+    }
+
+    #[test]
+    fn test_vector_4d_and_swizzles() {
+        let mut v = Vector::new4(1, 2, 3, 4);
+        assert_eq!((*v.x(), *v.y(), *v.z(), *v.w()), (1, 2, 3, 4));
+        *v.w_mut() = 10;
+        assert_eq!(v[3], 10);
+        assert_eq!(*v.xyz().z(), 3);
+
+        let p = Vector::new3(5, 6, 7);
+        assert_eq!((*p.xy().x(), *p.xy().y()), (5, 6));
+        assert_eq!((*p.xz().x(), *p.xz().y()), (5, 7));
+        assert_eq!((*p.yx().x(), *p.yx().y()), (6, 5));
+        assert_eq!(Vector::new2(1, 2).yx()[0], 2);
+
+        let zx: Vector<i32, 2> = p.swizzle([2, 0]);
+        assert_eq!((zx[0], zx[1]), (7, 5));
+    }
+
+    #[test]
+    fn test_vector_eq_hash_ord() {
+        let a = Vector::new2(1, 2);
+        let b = Vector::new2(1, 3);
+        let c = Vector::new2(0, 9);
+        assert_eq!(a, Vector::new2(1, 2));
+        assert_ne!(a, b);
+
+        let mut sorted = vec![b, a, c];
+        sorted.sort();
+        assert_eq!(sorted, vec![c, a, b]);
+
+        let mut set: VectorSet<i32, 2> = VectorSet::new();
+        assert!(set.insert(a));
+        assert!(!set.insert(Vector::new2(1, 2)));
+
+        let mut map: VectorMap<i32, 2, char> = VectorMap::new();
+        map.insert(c, '#');
+        assert_eq!(map.get(&Vector::new2(0, 9)), Some(&'#'));
+        assert_eq!(map.get(&a), None);
+
+        // the inherent min and max stay component-wise
+        assert_eq!(b.min(c), Vector::new2(0, 3));
+    }
+
+    #[test]
+    fn test_cross() {
+        let x: Vector<i64, 3> = [1, 0, 0].into();
+        let y: Vector<i64, 3> = [0, 1, 0].into();
+        let z = x.cross(y);
+        assert_eq!((z[0], z[1], z[2]), (0, 0, 1));
+        let w = y.cross(x);
+        assert_eq!(w[2], -1);
+    }
+
+    #[test]
+    fn test_matrix_mul_and_rotation() {
+        let up: Vector<i64, 2> = [0, -1].into();
+        let right = Matrix::rotation_90_cw() * up;
+        assert_eq!((*right.x(), *right.y()), (1, 0));
+        let back = Matrix::rotation_90_ccw() * right;
+        assert_eq!((*back.x(), *back.y()), (0, -1));
+
+        let full_turn = Matrix::<i64, 2, 2>::rotation_90_cw()
+            * Matrix::rotation_90_cw()
+            * Matrix::rotation_90_cw()
+            * Matrix::rotation_90_cw();
+        let identity = Matrix::<i64, 2, 2>::identity();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_eq!(full_turn[(i, j)], identity[(i, j)]);
+            }
+        }
+
+        let a: Matrix<i64, 2, 3> = [[1, 2, 3], [4, 5, 6]].into();
+        let b = a * a.transpose();
+        assert_eq!((b[(0, 0)], b[(0, 1)], b[(1, 1)]), (14, 32, 77));
+        let v = a * Vector::from([1, 1, 1]);
+        assert_eq!((v[0], v[1]), (6, 15));
+    }
+
+    #[test]
+    fn test_matrix_determinant() {
+        let a: Matrix<i64, 3, 3> = [[2, 0, 1], [1, 3, 2], [1, 1, 2]].into();
+        assert_eq!(a.determinant(), 6);
+        let b: Matrix<i64, 3, 3> = [[0, 1, 2], [1, 0, 3], [4, -3, 8]].into();
+        assert_eq!(b.determinant(), -2);
+        let singular: Matrix<i64, 2, 2> = [[1, 2], [2, 4]].into();
+        assert_eq!(singular.determinant(), 0);
+        let f: Matrix<f64, 2, 2> = [[0.5, 1.0], [2.0, 3.0]].into();
+        assert_eq!(f.determinant(), -0.5);
+        assert_eq!(Matrix::<i64, 4, 4>::identity().determinant(), 1);
+        assert_eq!(Matrix::<i8, 3, 3>::identity().determinant(), 1);
+        assert_eq!(Matrix::<i64, 0, 0>::identity().determinant(), 1);
+    }
+
+    #[test]
+    fn test_vector_min_max_index_sum() {
+        let a: Vector<i32, 2> = [1, 5].into();
+        let b: Vector<i32, 2> = [3, -2].into();
+        assert_eq!(*a.min(b).x(), 1);
+        assert_eq!(*a.min(b).y(), -2);
+        assert_eq!(*a.max(b).x(), 3);
+        assert_eq!(*a.max(b).y(), 5);
+
+        let mut c = a;
+        c[1] = 7;
+        assert_eq!(c[1], 7);
+
+        let total: Vector<i32, 2> = [a, b, c].into_iter().sum();
+        assert_eq!(total[0], 5);
+        assert_eq!(total[1], 10);
+    }
 }
@@ -0,0 +1,160 @@
+// Sums and maxima over independent records (lines, ranges, machines...) that
+// can run on every core. Rayon is only compiled in with the `parallel`
+// feature, without it Execution::Parallel runs sequentially too
+use std::fmt::Debug;
+use std::time::Instant;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Execution {
+    Sequential,
+    Parallel,
+}
+
+impl Default for Execution {
+    // parallel whenever it's compiled in
+    fn default() -> Self {
+        if cfg!(feature = "parallel") {
+            Execution::Parallel
+        } else {
+            Execution::Sequential
+        }
+    }
+}
+
+// ////////////// LOOPS /////////////////////
+// both take the amount of records and a closure solving the record at an
+// index, so the caller can keep its records in whatever it likes
+#[cfg(feature = "parallel")]
+pub fn sum<F>(execution: Execution, len: usize, f: F) -> u64
+where
+    F: Fn(usize) -> u64 + Sync + Send,
+{
+    match execution {
+        Execution::Sequential => (0..len).map(f).sum(),
+        Execution::Parallel => (0..len).into_par_iter().map(f).sum(),
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn sum<F>(_execution: Execution, len: usize, f: F) -> u64
+where
+    F: Fn(usize) -> u64 + Sync + Send,
+{
+    (0..len).map(f).sum()
+}
+
+// 0 when there are no records
+#[cfg(feature = "parallel")]
+pub fn max<F>(execution: Execution, len: usize, f: F) -> u64
+where
+    F: Fn(usize) -> u64 + Sync + Send,
+{
+    match execution {
+        Execution::Sequential => (0..len).map(f).max().unwrap_or(0),
+        Execution::Parallel => {
+            (0..len).into_par_iter().map(f).max().unwrap_or(0)
+        }
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn max<F>(_execution: Execution, len: usize, f: F) -> u64
+where
+    F: Fn(usize) -> u64 + Sync + Send,
+{
+    (0..len).map(f).max().unwrap_or(0)
+}
+
+// ////////////// SPEEDUP REPORT /////////////////////
+// Runs the solver both ways, checks they agree and prints how long each took
+pub fn report_speedup<T, F>(label: &str, solve: F) -> T
+where
+    T: PartialEq + Debug,
+    F: Fn(Execution) -> T,
+{
+    let start = Instant::now();
+    let sequential = solve(Execution::Sequential);
+    let sequential_time = start.elapsed();
+
+    let start = Instant::now();
+    let parallel = solve(Execution::Parallel);
+    let parallel_time = start.elapsed();
+
+    assert_eq!(
+        sequential, parallel,
+        "{}: the parallel result differs from the sequential one",
+        label
+    );
+    println!(
+        "{} - sequential {:.2?}, parallel {:.2?}, speedup {:.2}x{}",
+        label,
+        sequential_time,
+        parallel_time,
+        sequential_time.as_secs_f64() / parallel_time.as_secs_f64(),
+        if cfg!(feature = "parallel") {
+            ""
+        } else {
+            " (built without the parallel feature)"
+        }
+    );
+    parallel
+}
+
+// ////////////// TESTING /////////////////////
+// solves the same thing both ways and panics if the answers differ
+pub fn assert_agrees<T, F>(label: &str, solve: F)
+where
+    T: PartialEq + Debug,
+    F: Fn(Execution) -> T,
+{
+    assert_eq!(
+        solve(Execution::Sequential),
+        solve(Execution::Parallel),
+        "{}: the parallel result differs from the sequential one",
+        label
+    );
+}
+
+// A test that a day's `*_with` solvers agree both ways, on a generated input
+// of the given size. Goes inside the day's tests module. Without the feature
+// both ways are the same loop, but the `*_with` solvers still get run
+#[macro_export]
+macro_rules! parallel_tests {
+    ($day:expr, $size:expr, $($solver:ident),+ $(,)?) => {
+        #[test]
+        fn test_parallel_matches_sequential() {
+            let input = $crate::generate::puzzle_input(
+                $day,
+                $size,
+                &mut $crate::generate::Rng::new($day),
+            )
+            .unwrap();
+            $(
+                $crate::parallel::assert_agrees(stringify!($solver), |e| {
+                    $solver(&input, e)
+                });
+            )+
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_and_max_agree() {
+        let values: Vec<u64> = (0..1000).map(|i| (i * 7919) % 1013).collect();
+        for execution in [Execution::Sequential, Execution::Parallel] {
+            assert_eq!(
+                sum(execution, values.len(), |i| values[i]),
+                values.iter().sum::<u64>()
+            );
+            assert_eq!(max(execution, values.len(), |i| values[i]), 1012);
+            assert_eq!(max(execution, 0, |i| values[i]), 0);
+        }
+    }
+}
@@ -0,0 +1,446 @@
+// Parsing puzzle inputs straight from the bytes. A Cursor walks over the
+// input without copying anything, the pieces it hands out borrow from it, and
+// it keeps track of the line and column so a bad input says where it's bad
+use std::fmt;
+use std::io;
+
+// ////////////// ERRORS /////////////////////
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,   // starting at 1
+    pub column: usize, // starting at 1, in bytes
+    pub expected: String,
+    pub found: Option<u8>, // None at the end of the input
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        match self.found {
+            None => write!(f, "the end of the input"),
+            Some(b'\n') | Some(b'\r') => write!(f, "the end of the line"),
+            Some(b) if b.is_ascii_graphic() || b == b' ' => {
+                write!(f, "'{}'", b as char)
+            }
+            Some(b) => write!(f, "byte 0x{:02x}", b),
+        }
+    }
+}
+
+impl ParseError {
+    // says what was being parsed, like "expected ')' for buttons"
+    pub fn context(mut self, what: &str) -> Self {
+        self.expected = format!("{} for {}", self.expected, what);
+        self
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// so the streaming solvers can hand a bad record back as an io error
+impl From<ParseError> for io::Error {
+    fn from(e: ParseError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+// ////////////// CURSOR /////////////////////
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    input: &'a [u8],
+    pos: usize,
+    line: usize,
+    line_start: usize, // pos of the first byte of the current line
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Cursor::at_line(input, 1)
+    }
+
+    // for input that is handed out line by line, so the errors still point
+    // at the right line of the whole file
+    pub fn at_line(input: &'a str, line: usize) -> Self {
+        Cursor {
+            input: input.as_bytes(),
+            pos: 0,
+            line,
+            line_start: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    pub fn rest(&self) -> &'a [u8] {
+        &self.input[self.pos..]
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    // (line, column) of the next byte
+    pub fn position(&self) -> (usize, usize) {
+        (self.line, self.pos - self.line_start + 1)
+    }
+
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let (line, column) = self.position();
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+            found: self.peek(),
+        }
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        if b == b'\n' {
+            self.line += 1;
+            self.line_start = self.pos;
+        }
+        Some(b)
+    }
+
+    // ////////////// SINGLE BYTES /////////////////////
+    // consumes the byte if it's the next one
+    pub fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect(&mut self, byte: u8) -> Result<(), ParseError> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.error(format!("'{}'", byte as char)))
+        }
+    }
+
+    pub fn expect_str(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.rest().starts_with(literal.as_bytes()) {
+            for _ in 0..literal.len() {
+                self.bump();
+            }
+            Ok(())
+        } else {
+            Err(match literal.as_bytes() {
+                &[byte] => self.error(format!("'{}'", byte as char)),
+                _ => self.error(format!("\"{}\"", literal)),
+            })
+        }
+    }
+
+    pub fn skip_spaces(&mut self) {
+        while self.eat(b' ') {}
+    }
+
+    // a \n or \r\n, or nothing left at all since the last line doesn't always
+    // have one
+    pub fn line_end(&mut self) -> Result<(), ParseError> {
+        self.eat(b'\r');
+        if self.eat(b'\n') || self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("the end of the line"))
+        }
+    }
+
+    // ////////////// RUNS OF BYTES /////////////////////
+    pub fn take_while(&mut self, mut keep: impl FnMut(u8) -> bool) -> &'a [u8] {
+        let start = self.pos;
+        while self.peek().is_some_and(&mut keep) {
+            self.bump();
+        }
+        &self.input[start..self.pos]
+    }
+
+    // everything up to the delimiter, which is left for the caller, or up to
+    // the end if it never shows up
+    pub fn take_until(&mut self, delimiter: u8) -> &'a [u8] {
+        self.take_while(|b| b != delimiter)
+    }
+
+    // ////////////// NUMBERS /////////////////////
+    pub fn uint(&mut self) -> Result<u64, ParseError> {
+        let start = self.clone();
+        let digits = self.take_while(|b| b.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(self.error("a number"));
+        }
+        digits
+            .iter()
+            .try_fold(0u64, |n, &d| {
+                n.checked_mul(10)?.checked_add((d - b'0') as u64)
+            })
+            .ok_or_else(|| start.error("a number that fits in a u64"))
+    }
+
+    pub fn int(&mut self) -> Result<i64, ParseError> {
+        let start = self.clone();
+        let negative = self.eat(b'-');
+        if !negative {
+            self.eat(b'+');
+        }
+        let magnitude = self.uint()?;
+        let out_of_range = || start.error("a number that fits in an i64");
+        if negative {
+            0i64.checked_sub_unsigned(magnitude)
+                .ok_or_else(out_of_range)
+        } else {
+            i64::try_from(magnitude).map_err(|_| out_of_range())
+        }
+    }
+
+    // ////////////// LISTS /////////////////////
+    // one or more items with the separator between them, like 1,3,5
+    pub fn separated_list<T>(
+        &mut self,
+        separator: u8,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![item(self)?];
+        while self.eat(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+}
+
+// ////////////// COMBINATORS /////////////////////
+// Parsers are just functions taking the cursor, these build bigger ones out
+// of smaller ones. Mostly meant for the fields of line_grammar!
+
+// one or more items with the separator between them
+pub fn list<'a, T>(
+    separator: u8,
+    mut item: impl FnMut(&mut Cursor<'a>) -> Result<T, ParseError>,
+) -> impl FnMut(&mut Cursor<'a>) -> Result<Vec<T>, ParseError> {
+    move |cursor| cursor.separated_list(separator, &mut item)
+}
+
+// the item between two literals, like the 1,3 in (1,3)
+pub fn delimited<'a, T>(
+    open: &'static str,
+    mut item: impl FnMut(&mut Cursor<'a>) -> Result<T, ParseError>,
+    close: &'static str,
+) -> impl FnMut(&mut Cursor<'a>) -> Result<T, ParseError> {
+    move |cursor| {
+        cursor.expect_str(open)?;
+        let value = item(cursor)?;
+        cursor.expect_str(close)?;
+        Ok(value)
+    }
+}
+
+// Zero or more items back to back. An item that fails right away ends the
+// list, one that fails after it got going is a real error and comes out
+pub fn repeated<'a, T>(
+    mut item: impl FnMut(&mut Cursor<'a>) -> Result<T, ParseError>,
+) -> impl FnMut(&mut Cursor<'a>) -> Result<Vec<T>, ParseError> {
+    move |cursor| {
+        let mut items = Vec::new();
+        loop {
+            let mut attempt = cursor.clone();
+            match item(&mut attempt) {
+                Ok(value) => {
+                    items.push(value);
+                    *cursor = attempt;
+                }
+                Err(e) if (e.line, e.column) == cursor.position() => {
+                    return Ok(items);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+pub fn map<'a, T, U>(
+    mut item: impl FnMut(&mut Cursor<'a>) -> Result<T, ParseError>,
+    mut f: impl FnMut(T) -> U,
+) -> impl FnMut(&mut Cursor<'a>) -> Result<U, ParseError> {
+    move |cursor| item(cursor).map(&mut f)
+}
+
+// ////////////// LINE GRAMMARS /////////////////////
+// Describes a whole line as a sequence of string literals, that have to be
+// there as they are, and fields, each with its type and the parser for it:
+//
+//     line_grammar! {
+//         struct Move {
+//             "move ",
+//             amount: u64 = Cursor::uint,
+//             " to ",
+//             targets: Vec<u64> = parse::list(b',', Cursor::uint),
+//         }
+//     }
+//
+// This defines the struct and a Move::parse(cursor) that reads one line,
+// line ending included. Errors say which field they happened in. A struct
+// with a lifetime, like Move<'a>, can keep &'a str fields borrowed from the
+// input
+#[macro_export]
+macro_rules! line_grammar {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident $(<$lt:lifetime>)? { $($body:tt)* }
+    ) => {
+        $crate::line_grammar!(
+            @munch [$(#[$meta])* $vis struct $name $(<$lt>)?] [] []
+            $($body)*
+        );
+    };
+
+    // a literal
+    (
+        @munch $head:tt [$($fields:tt)*] [$($steps:tt)*]
+        $literal:literal $(, $($rest:tt)*)?
+    ) => {
+        $crate::line_grammar!(
+            @munch $head [$($fields)*] [$($steps)* (literal $literal)]
+            $($($rest)*)?
+        );
+    };
+
+    // a field
+    (
+        @munch $head:tt [$($fields:tt)*] [$($steps:tt)*]
+        $field:ident : $ty:ty = $parser:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::line_grammar!(
+            @munch $head [$($fields)* ($field $ty)]
+            [$($steps)* (field $field $parser)]
+            $($($rest)*)?
+        );
+    };
+
+    // nothing left, write it all out
+    (
+        @munch [$(#[$meta:meta])* $vis:vis struct $name:ident $(<$lt:lifetime>)?]
+        [$(($field:ident $ty:ty))*] [$($step:tt)*]
+    ) => {
+        $(#[$meta])*
+        $vis struct $name $(<$lt>)? {
+            $($field: $ty,)*
+        }
+
+        impl $(<$lt>)? $name $(<$lt>)? {
+            pub fn parse(
+                cursor: &mut $crate::parse::Cursor $(<$lt>)?,
+            ) -> Result<Self, $crate::parse::ParseError> {
+                $($crate::line_grammar! { @step cursor $step })*
+                cursor.line_end()?;
+                Ok($name { $($field,)* })
+            }
+        }
+    };
+
+    (@step $cursor:ident (literal $literal:literal)) => {
+        $cursor.expect_str($literal)?;
+    };
+
+    (@step $cursor:ident (field $field:ident $parser:expr)) => {
+        let $field = ($parser)(&mut *$cursor)
+            .map_err(|e| e.context(stringify!($field)))?;
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_and_lists() {
+        let mut cursor = Cursor::new("12,-3,+4 rest");
+        assert_eq!(
+            cursor.separated_list(b',', Cursor::int),
+            Ok(vec![12, -3, 4])
+        );
+        cursor.skip_spaces();
+        assert_eq!(cursor.take_until(b'x'), b"rest");
+        assert!(cursor.is_empty());
+        assert_eq!(Cursor::new("-9223372036854775808").int(), Ok(i64::MIN));
+    }
+
+    line_grammar! {
+        #[derive(Debug, PartialEq)]
+        struct Move {
+            "move ",
+            amount: u64 = Cursor::uint,
+            " to",
+            targets: Vec<Vec<i64>> =
+                repeated(delimited(" (", list(b',', Cursor::int), ")")),
+        }
+    }
+
+    #[test]
+    fn test_line_grammar() {
+        let mut cursor = Cursor::new("move 3 to (1,-2) (4)\nmove 0 to\n");
+        assert_eq!(
+            Move::parse(&mut cursor),
+            Ok(Move {
+                amount: 3,
+                targets: vec![vec![1, -2], vec![4]]
+            })
+        );
+        assert_eq!(
+            Move::parse(&mut cursor),
+            Ok(Move {
+                amount: 0,
+                targets: vec![]
+            })
+        );
+        assert!(cursor.is_empty());
+
+        let error = |line| Move::parse(&mut Cursor::new(line)).unwrap_err();
+        assert_eq!(
+            error("move 3 to (1,x)").to_string(),
+            "line 1, column 14: expected a number for targets, found 'x'"
+        );
+        assert_eq!(
+            error("mov 3").to_string(),
+            "line 1, column 1: expected \"move \", found 'm'"
+        );
+        assert_eq!(
+            error("move 3 to (1) [2]").to_string(),
+            "line 1, column 14: expected the end of the line, found ' '"
+        );
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let mut cursor = Cursor::new("1,2\n3;4");
+        cursor.separated_list(b',', Cursor::uint).unwrap();
+        cursor.line_end().unwrap();
+        assert_eq!(cursor.uint(), Ok(3));
+        let e = cursor.expect(b',').unwrap_err();
+        assert_eq!((e.line, e.column, e.found), (2, 2, Some(b';')));
+        assert_eq!(e.to_string(), "line 2, column 2: expected ',', found ';'");
+
+        let mut cursor = Cursor::at_line("(1,", 7);
+        cursor.expect(b'(').unwrap();
+        let e = cursor.separated_list(b',', Cursor::uint).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 7, column 4: expected a number, found the end of the input"
+        );
+        let e = Cursor::new("99999999999999999999").uint().unwrap_err();
+        assert_eq!(
+            (e.column, e.expected.as_str()),
+            (1, "a number that fits in a u64")
+        );
+    }
+}
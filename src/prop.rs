@@ -0,0 +1,137 @@
+// A tiny property testing harness: run a property on a bunch of generated
+// values and, when one fails, shrink it down to something small enough to
+// read before reporting it
+use crate::generate::Rng;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+// base seed, AOC_PROP_SEED overrides it to explore other cases
+const DEFAULT_SEED: u64 = 0x2025;
+
+// ////////////// SHRINKING /////////////////////
+// Candidates are simpler versions of the value, the most aggressive first
+pub trait Shrink: Sized {
+    fn shrink(&self) -> Vec<Self>;
+}
+
+impl Shrink for u64 {
+    // towards zero: 0, then halfway, then one less
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        for c in [0, self / 2, self.saturating_sub(1)] {
+            if c < *self && !candidates.contains(&c) {
+                candidates.push(c);
+            }
+        }
+        candidates
+    }
+}
+
+impl Shrink for usize {
+    fn shrink(&self) -> Vec<Self> {
+        (*self as u64)
+            .shrink()
+            .into_iter()
+            .map(|c| c as usize)
+            .collect()
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    // drop the back half, then single elements, then shrink the elements
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        if self.len() > 1 {
+            candidates.push(self[..self.len() / 2].to_vec());
+        }
+        for i in 0..self.len() {
+            let mut smaller = self.clone();
+            smaller.remove(i);
+            candidates.push(smaller);
+        }
+        for i in 0..self.len() {
+            for element in self[i].shrink() {
+                let mut smaller = self.clone();
+                smaller[i] = element;
+                candidates.push(smaller);
+            }
+        }
+        candidates
+    }
+}
+
+// ////////////// CHECKING /////////////////////
+// a panic inside the property counts as a failure too
+fn holds<T, P: Fn(&T) -> bool>(property: &P, value: &T) -> bool {
+    panic::catch_unwind(AssertUnwindSafe(|| property(value))).unwrap_or(false)
+}
+
+// Greedily takes the first candidate that still fails until none do
+pub fn shrink_failure<T, P>(mut value: T, property: &P) -> (T, usize)
+where
+    T: Shrink,
+    P: Fn(&T) -> bool,
+{
+    let mut steps = 0;
+    while let Some(smaller) =
+        value.shrink().into_iter().find(|c| !holds(property, c))
+    {
+        value = smaller;
+        steps += 1;
+    }
+    (value, steps)
+}
+
+// Runs the property on `cases` generated values and panics with the shrunk
+// counterexample and the seed that produced it
+pub fn check<T, G, P>(cases: usize, mut generate: G, property: P)
+where
+    T: Shrink + Debug,
+    G: FnMut(&mut Rng) -> T,
+    P: Fn(&T) -> bool,
+{
+    let seed = std::env::var("AOC_PROP_SEED")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_SEED);
+    for case in 0..cases as u64 {
+        let case_seed = seed.wrapping_add(case);
+        let value = generate(&mut Rng::new(case_seed));
+        if holds(&property, &value) {
+            continue;
+        }
+        let (smallest, steps) = shrink_failure(value, &property);
+        panic!(
+            "property failed for seed {} (case {}), shrunk {} times to:\n{:#?}",
+            case_seed, case, steps, smallest
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrink_finds_smallest_failure() {
+        // fails for any list with a number of at least 10 in it
+        let property = |v: &Vec<u64>| v.iter().all(|&n| n < 10);
+        let (smallest, _) = shrink_failure(vec![3, 1, 40, 7, 12, 5], &property);
+        assert_eq!(smallest, vec![10]);
+    }
+
+    #[test]
+    fn test_check_passes() {
+        check(50, |rng| rng.range(0, 100), |&n| n <= 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "shrunk")]
+    fn test_check_reports_panics() {
+        check(
+            50,
+            |rng| rng.range(0, 100),
+            |&n| n < 50 || panic!("too big"),
+        );
+    }
+}
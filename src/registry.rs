@@ -0,0 +1,6 @@
+// Days that have a solver in src/bin/dayNN.rs, `aoc new` adds new days here
+pub const DAYS: &[u32] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+
+pub fn has_solver(day: u32) -> bool {
+    DAYS.contains(&day)
+}
@@ -0,0 +1,160 @@
+// Generates the files for a new day: the solver in src/bin/dayNN.rs, its
+// entry in the registry, the example in examples/dayNN and an empty input file
+
+pub fn day_path(day: u32) -> String {
+    format!("src/bin/day{:02}.rs", day)
+}
+
+// build.rs turns the files in here into the day's example tests
+pub fn example_paths(day: u32) -> (String, String) {
+    (
+        format!("examples/day{:02}/puzzle.txt", day),
+        format!("examples/day{:02}/puzzle.expected", day),
+    )
+}
+
+// the answers are commented out so no tests are generated for them, a test
+// checking the template's 0 would pass without anything being solved
+pub const EXPECTED_TEMPLATE: &str =
+    "# TODO put the answers for the example from the puzzle text and uncomment
+# part_one: <answer>
+# part_two: <answer>
+";
+
+pub fn render_day(day: u32) -> String {
+    format!(
+        r#"use advent_of_code_2025::utils;
+
+fn read_input(input: &str) -> Vec<&str> {{
+    input.lines().collect()
+}}
+
+fn part_one(input: &str) -> u64 {{
+    let _lines = read_input(input);
+    0
+}}
+
+fn part_two(input: &str) -> u64 {{
+    let _lines = read_input(input);
+    0
+}}
+
+fn main() {{
+    match utils::read_input("day{day:02}.txt") {{
+        Ok(input) => {{
+            println!("Day {day:02} - Part 1: {{}}", part_one(input.as_str()));
+            println!("Day {day:02} - Part 2: {{}}", part_two(input.as_str()));
+        }}
+        Err(e) => {{
+            eprintln!("Error reading input file: {{}}", e);
+            eprintln!("Make sure 'inputs/day{day:02}.txt' exists.");
+        }}
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    // unused until the answers in examples/day{day:02} are filled in
+    #[allow(unused_imports)]
+    use super::*;
+
+    advent_of_code_2025::example_tests!();
+}}
+"#
+    )
+}
+
+// Adds the day to the DAYS list in the source of src/registry.rs, keeping it
+// sorted. None if the list isn't there or already has the day
+pub fn register_day(registry_source: &str, day: u32) -> Option<String> {
+    let start = registry_source.find("DAYS: &[u32] = &[")?;
+    let list_start = start + "DAYS: &[u32] = &[".len();
+    let list_end = list_start + registry_source[list_start..].find(']')?;
+
+    let mut days: Vec<u32> = registry_source[list_start..list_end]
+        .split(',')
+        .filter_map(|d| d.trim().parse().ok())
+        .collect();
+    if days.contains(&day) {
+        return None;
+    }
+    days.push(day);
+    days.sort();
+
+    let list: Vec<String> = days.iter().map(|d| d.to_string()).collect();
+    Some(format!(
+        "{}{}{}",
+        &registry_source[..list_start],
+        list.join(", "),
+        &registry_source[list_end..]
+    ))
+}
+
+// The puzzle pages have the example in the first <pre><code> block after
+// "For example", with some <em> tags and html entities sprinkled in
+pub fn extract_example(html: &str) -> Option<String> {
+    let from = html.find("For example").unwrap_or(0);
+    let html = &html[from..];
+    let start = html.find("<pre><code>")? + "<pre><code>".len();
+    let end = start + html[start..].find("</code></pre>")?;
+
+    let mut example = String::new();
+    let mut in_tag = false;
+    for c in html[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => example.push(c),
+            _ => {}
+        }
+    }
+    Some(
+        example
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_day() {
+        let source = render_day(12);
+        assert!(source.contains("utils::read_input(\"day12.txt\")"));
+        assert!(source.contains("println!(\"Day 12 - Part 2: {}\""));
+        assert!(source.contains("example_tests!();"));
+        assert!(!source.contains("bad_add"));
+        assert_eq!(
+            example_paths(12),
+            (
+                "examples/day12/puzzle.txt".to_string(),
+                "examples/day12/puzzle.expected".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_register_day() {
+        let source = "// comment\npub const DAYS: &[u32] = &[1, 2, 11];\n";
+        assert_eq!(
+            register_day(source, 5).unwrap(),
+            "// comment\npub const DAYS: &[u32] = &[1, 2, 5, 11];\n"
+        );
+        assert_eq!(register_day(source, 2), None);
+        assert_eq!(register_day("nothing here", 2), None);
+    }
+
+    #[test]
+    fn test_extract_example() {
+        let html = "<article><p>Intro <code>x</code></p><pre><code>not \
+                    this</code></pre><p>For example:</p><pre><code>..@@.\n\
+                    <em>@</em>&lt;.&gt;\n</code></pre></article>";
+        assert_eq!(extract_example(html).unwrap(), "..@@.\n@<.>\n");
+        assert_eq!(extract_example("<p>no code</p>"), None);
+    }
+}
@@ -0,0 +1,263 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::bitset::BitSet;
+
+// ////////////// RESULT /////////////////////
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult<S, C> {
+    pub cost: C,
+    pub path: Vec<S>, // from the start state to the goal, both included
+}
+
+// Every discovered state is pushed once into an arena together with the
+// index of the state it was reached from, so the path is rebuilt by walking
+// the parents back from the goal
+struct Arena<S> {
+    nodes: Vec<(S, usize)>,
+}
+
+impl<S: Clone> Arena<S> {
+    fn new(start: S) -> Self {
+        Arena {
+            nodes: vec![(start, usize::MAX)],
+        }
+    }
+
+    fn push(&mut self, state: S, parent: usize) -> usize {
+        self.nodes.push((state, parent));
+        self.nodes.len() - 1
+    }
+
+    fn path_to(&self, mut idx: usize) -> Vec<S> {
+        let mut path = Vec::new();
+        while idx != usize::MAX {
+            path.push(self.nodes[idx].0.clone());
+            idx = self.nodes[idx].1;
+        }
+        path.reverse();
+        path
+    }
+}
+
+// ////////////// VISITED SET STRATEGIES /////////////////////
+pub trait VisitedSet<S> {
+    // returns true if the state wasn't visited before
+    fn insert(&mut self, state: &S) -> bool;
+}
+
+impl<S: Hash + Eq + Clone> VisitedSet<S> for HashSet<S> {
+    fn insert(&mut self, state: &S) -> bool {
+        HashSet::insert(self, state.clone())
+    }
+}
+
+// For small integer states, like bitmasks, one bit per possible state is
+// way cheaper than hashing. The set grows up to the biggest state seen, that
+// is state / 8 bytes, so this is only for states that stay small (day10's
+// machines have about ten lights, so around 2^10 states). Panics if a state
+// doesn't fit in a usize
+impl VisitedSet<u64> for BitSet {
+    fn insert(&mut self, state: &u64) -> bool {
+        let state =
+            usize::try_from(*state).expect("State too big for a BitSet");
+        BitSet::insert(self, state)
+    }
+}
+
+impl VisitedSet<usize> for BitSet {
+    fn insert(&mut self, state: &usize) -> bool {
+        BitSet::insert(self, *state)
+    }
+}
+
+// ////////////// BFS /////////////////////
+// Unweighted shortest path, the cost is the amount of steps taken
+pub fn bfs<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, usize>>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+{
+    bfs_with(start, HashSet::new(), successors, is_goal)
+}
+
+pub fn bfs_with<S, I>(
+    start: S,
+    mut visited: impl VisitedSet<S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, usize>>
+where
+    S: Clone,
+    I: IntoIterator<Item = S>,
+{
+    visited.insert(&start);
+    let mut arena = Arena::new(start);
+    let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+    queue.push_back((0, 0));
+
+    while let Some((idx, depth)) = queue.pop_front() {
+        if is_goal(&arena.nodes[idx].0) {
+            return Some(SearchResult {
+                cost: depth,
+                path: arena.path_to(idx),
+            });
+        }
+        for next in successors(&arena.nodes[idx].0) {
+            if visited.insert(&next) {
+                let next_idx = arena.push(next, idx);
+                queue.push_back((next_idx, depth + 1));
+            }
+        }
+    }
+    None
+}
+
+// ////////////// DIJKSTRA AND A* /////////////////////
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+// heuristic has to never overestimate the remaining cost to a goal, or the
+// path found might not be the shortest one
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // best known cost and arena index for every state discovered so far
+    let mut best: HashMap<S, (C, usize)> = HashMap::new();
+    best.insert(start.clone(), (C::default(), 0));
+    let mut heap: BinaryHeap<Reverse<(C, C, usize)>> = BinaryHeap::new();
+    heap.push(Reverse((heuristic(&start), C::default(), 0)));
+    let mut arena = Arena::new(start);
+
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        let state = arena.nodes[idx].0.clone();
+        // stale entry, a cheaper way to this state was found after pushing it
+        if best[&state].1 != idx {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(SearchResult {
+                cost,
+                path: arena.path_to(idx),
+            });
+        }
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            let improves = match best.entry(next.clone()) {
+                Entry::Occupied(e) => next_cost < e.get().0,
+                Entry::Vacant(_) => true,
+            };
+            if improves {
+                let estimate = next_cost + heuristic(&next);
+                let next_idx = arena.push(next.clone(), idx);
+                best.insert(next, (next_cost, next_idx));
+                heap.push(Reverse((estimate, next_cost, next_idx)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // S is the start, E the end and # are walls
+    const MAZE: &str = "S.#.....\n..#.##.#\n....#..E\n";
+
+    fn maze_successors(
+        maze: &[&[u8]],
+        (i, j): (usize, usize),
+    ) -> Vec<(usize, usize)> {
+        let mut next = Vec::new();
+        for (di, dj) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let (ni, nj) = (i as isize + di, j as isize + dj);
+            if ni < 0 || nj < 0 {
+                continue;
+            }
+            let (ni, nj) = (ni as usize, nj as usize);
+            if ni < maze.len() && nj < maze[ni].len() && maze[ni][nj] != b'#' {
+                next.push((ni, nj));
+            }
+        }
+        next
+    }
+
+    #[test]
+    fn test_bfs_maze() {
+        let maze: Vec<&[u8]> = MAZE.lines().map(|l| l.as_bytes()).collect();
+        let result =
+            bfs((0, 0), |&p| maze_successors(&maze, p), |&p| p == (2, 7))
+                .unwrap();
+        assert_eq!(result.cost, 13);
+        assert_eq!(result.path.len(), 14);
+        assert_eq!(result.path[0], (0, 0));
+        assert_eq!(result.path[13], (2, 7));
+        assert!(
+            bfs((0, 0), |&p| maze_successors(&maze, p), |_| false).is_none()
+        );
+    }
+
+    #[test]
+    fn test_bfs_bit_visited() {
+        // flip bits with masks until reaching 0b1101
+        let masks = [0b0011u64, 0b0110, 0b1000];
+        let result = bfs_with(
+            0u64,
+            BitSet::new(),
+            |&s| masks.iter().map(move |m| s ^ m),
+            |&s| s == 0b1101,
+        )
+        .unwrap();
+        assert_eq!(result.cost, 3);
+        assert_eq!(result.path.first(), Some(&0));
+        assert_eq!(result.path.last(), Some(&0b1101));
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        // going straight from 0 to 3 is more expensive than going around
+        let edges: Vec<Vec<(u32, u64)>> =
+            vec![vec![(1, 1), (3, 10)], vec![(2, 2)], vec![(3, 3)], vec![]];
+        let result =
+            dijkstra(0u32, |&n| edges[n as usize].clone(), |&n| n == 3)
+                .unwrap();
+        assert_eq!(result.cost, 6);
+        assert_eq!(result.path, vec![0, 1, 2, 3]);
+
+        let maze: Vec<&[u8]> = MAZE.lines().map(|l| l.as_bytes()).collect();
+        let result = astar(
+            (0usize, 0usize),
+            |&p| maze_successors(&maze, p).into_iter().map(|n| (n, 1)),
+            |&(i, j)| 2usize.abs_diff(i) + 7usize.abs_diff(j),
+            |&p| p == (2, 7),
+        )
+        .unwrap();
+        assert_eq!(result.cost, 13);
+    }
+}
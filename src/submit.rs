@@ -0,0 +1,546 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+
+// Everything around submitting answers that doesn't need the network:
+// running a day's solver, understanding what the site answered and keeping a
+// history of every attempt so known-wrong answers are never sent twice
+
+// every attempt is appended here, the file is in .gitignore
+pub const HISTORY_FILE: &str = ".aoc_history";
+
+// ////////////// OUTCOME /////////////////////
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    Wait { seconds: Option<u64> },
+    WrongLevel, // already solved, or part one isn't solved yet
+    Unknown,
+}
+
+impl Outcome {
+    fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+
+    fn tag(&self) -> String {
+        match self {
+            Outcome::Correct => "correct".to_string(),
+            Outcome::Incorrect => "incorrect".to_string(),
+            Outcome::TooHigh => "too-high".to_string(),
+            Outcome::TooLow => "too-low".to_string(),
+            Outcome::Wait { seconds: Some(s) } => format!("wait-{}", s),
+            Outcome::Wait { seconds: None } => "wait".to_string(),
+            Outcome::WrongLevel => "wrong-level".to_string(),
+            Outcome::Unknown => "unknown".to_string(),
+        }
+    }
+
+    fn from_tag(tag: &str) -> Outcome {
+        match tag {
+            "correct" => Outcome::Correct,
+            "incorrect" => Outcome::Incorrect,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            "wait" => Outcome::Wait { seconds: None },
+            "wrong-level" => Outcome::WrongLevel,
+            _ => match tag.strip_prefix("wait-") {
+                Some(s) => Outcome::Wait {
+                    seconds: s.parse().ok(),
+                },
+                None => Outcome::Unknown,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::Incorrect => write!(f, "That's not the right answer"),
+            Outcome::TooHigh => write!(f, "Wrong, the answer is too high"),
+            Outcome::TooLow => write!(f, "Wrong, the answer is too low"),
+            Outcome::Wait { seconds: Some(s) } => {
+                write!(f, "Answered too recently, wait {}s", s)
+            }
+            Outcome::Wait { seconds: None } => {
+                write!(f, "Answered too recently, wait a bit")
+            }
+            Outcome::WrongLevel => {
+                write!(f, "Not the right level, already solved?")
+            }
+            Outcome::Unknown => write!(f, "Couldn't understand the response"),
+        }
+    }
+}
+
+// parses the sentences the site puts in the <article> of the answer page
+pub fn parse_response(body: &str) -> Outcome {
+    if body.contains("That's the right answer") {
+        Outcome::Correct
+    } else if body.contains("That's not the right answer") {
+        if body.contains("too high") {
+            Outcome::TooHigh
+        } else if body.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        }
+    } else if body.contains("You gave an answer too recently") {
+        Outcome::Wait {
+            seconds: parse_wait(body),
+        }
+    } else if body.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown
+    }
+}
+
+// "You have 1m 23s left to wait" or "You have 37s left to wait"
+fn parse_wait(body: &str) -> Option<u64> {
+    let (before, _) = body.split_once(" left to wait")?;
+    let (_, amount) = before.rsplit_once("You have ")?;
+    let mut seconds = 0;
+    for part in amount.split_whitespace() {
+        let (n, unit) = part.split_at(part.len() - 1);
+        let n: u64 = n.parse().ok()?;
+        seconds += match unit {
+            "h" => n * 3600,
+            "m" => n * 60,
+            "s" => n,
+            _ => return None,
+        };
+    }
+    Some(seconds)
+}
+
+// A wrong answer also starts a wait, "Please wait one minute before trying
+// again." and after a few wrong ones "please wait 5 minutes before trying
+// again."
+pub fn parse_cooldown(body: &str) -> Option<u64> {
+    let lower = body.to_ascii_lowercase();
+    let (_, rest) = lower.split_once("please wait ")?;
+    let (amount, _) = rest.split_once(" before trying again")?;
+    let (n, unit) = amount.split_once(' ')?;
+    let n: u64 = match n {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+    match unit.trim_end_matches('s') {
+        "second" => Some(n),
+        "minute" => Some(n * 60),
+        "hour" => Some(n * 3600),
+        _ => None,
+    }
+}
+
+// ////////////// HISTORY /////////////////////
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attempt {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Outcome,
+    pub timestamp: u64,        // unix seconds
+    pub cooldown: Option<u64>, // seconds to wait after a wrong answer
+}
+
+// why an answer won't be submitted
+#[derive(Debug, Clone, PartialEq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong(Outcome),
+    NotBelow(String), // an answer this big or bigger was already too high
+    NotAbove(String), // an answer this small or smaller was already too low
+    RateLimited { seconds_left: u64 },
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refusal::AlreadySolved(a) => {
+                write!(f, "already solved, the answer was {}", a)
+            }
+            Refusal::KnownWrong(o) => {
+                write!(f, "already submitted before: {}", o)
+            }
+            Refusal::NotBelow(a) => write!(f, "{} was already too high", a),
+            Refusal::NotAbove(a) => write!(f, "{} was already too low", a),
+            Refusal::RateLimited { seconds_left } => {
+                write!(f, "rate limited, {}s left to wait", seconds_left)
+            }
+        }
+    }
+}
+
+// One attempt per line, tab separated: day, part, answer, outcome, time and
+// cooldown. The cooldown is empty when there wasn't one, and missing in
+// files from before it was kept
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        let mut attempts = Vec::new();
+        for l in contents.lines().filter(|l| !l.trim().is_empty()) {
+            let fields: Vec<&str> = l.split('\t').collect();
+            let malformed = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("malformed history line {:?}", l),
+                )
+            };
+            if fields.len() != 5 && fields.len() != 6 {
+                return Err(malformed());
+            }
+            attempts.push(Attempt {
+                day: fields[0].parse().map_err(|_| malformed())?,
+                part: fields[1].parse().map_err(|_| malformed())?,
+                answer: fields[2].to_string(),
+                outcome: Outcome::from_tag(fields[3]),
+                timestamp: fields[4].parse().map_err(|_| malformed())?,
+                cooldown: match fields.get(5) {
+                    None | Some(&"") => None,
+                    Some(c) => Some(c.parse().map_err(|_| malformed())?),
+                },
+            });
+        }
+        Ok(History { attempts })
+    }
+
+    pub fn record(&mut self, path: &Path, attempt: Attempt) -> io::Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            attempt.day,
+            attempt.part,
+            attempt.answer,
+            attempt.outcome.tag(),
+            attempt.timestamp,
+            attempt.cooldown.map(|c| c.to_string()).unwrap_or_default()
+        )?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    pub fn check(
+        &self,
+        day: u32,
+        part: u32,
+        answer: &str,
+        now: u64,
+    ) -> Result<(), Refusal> {
+        // the site rate limits every submission, not just the ones for this
+        // day, so the last wait applies to everything. A wrong answer makes
+        // you wait too, at least a minute
+        let wait = self.attempts.last().and_then(|last| match last.outcome {
+            Outcome::Wait { seconds } => Some((last, seconds.unwrap_or(60))),
+            _ if last.outcome.is_wrong() => {
+                Some((last, last.cooldown.unwrap_or(60)))
+            }
+            _ => None,
+        });
+        if let Some((last, seconds)) = wait {
+            let until = last.timestamp + seconds;
+            if now < until {
+                return Err(Refusal::RateLimited {
+                    seconds_left: until - now,
+                });
+            }
+        }
+
+        let number: Option<i128> = answer.parse().ok();
+        for a in self
+            .attempts
+            .iter()
+            .filter(|a| a.day == day && a.part == part)
+        {
+            if a.outcome == Outcome::Correct {
+                return Err(Refusal::AlreadySolved(a.answer.clone()));
+            }
+            if a.answer == answer && a.outcome.is_wrong() {
+                return Err(Refusal::KnownWrong(a.outcome.clone()));
+            }
+            let (Some(n), Ok(previous)) = (number, a.answer.parse::<i128>())
+            else {
+                continue;
+            };
+            if a.outcome == Outcome::TooHigh && n >= previous {
+                return Err(Refusal::NotBelow(a.answer.clone()));
+            }
+            if a.outcome == Outcome::TooLow && n <= previous {
+                return Err(Refusal::NotAbove(a.answer.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+// ////////////// RUNNING THE SOLVERS /////////////////////
+// the days print "Day NN - Part P: answer", the first line for the part is
+// the answer, extra lines like "Part 2 bfs" are alternative solutions
+pub fn parse_solver_output(
+    output: &str,
+    day: u32,
+    part: u32,
+) -> Option<String> {
+    let prefix = format!("Day {:02} - Part {}", day, part);
+    output
+        .lines()
+        .filter_map(|l| l.strip_prefix(&prefix))
+        .find_map(|rest| rest.rsplit_once(':'))
+        .map(|(_, answer)| answer.trim().to_string())
+}
+
+pub fn run_solver(day: u32, part: u32) -> io::Result<Option<String>> {
+    // CARGO is set when running through cargo run, use that same cargo
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["run", "--quiet", "--release", "--bin"])
+        .arg(format!("day{:02}", day))
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "day{:02} failed: {}",
+            day,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(parse_solver_output(
+        &String::from_utf8_lossy(&output.stdout),
+        day,
+        part,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response(
+                "<article><p>That's the right answer! You are one gold star \
+                 closer.</p></article>"
+            ),
+            Outcome::Correct
+        );
+        assert_eq!(
+            parse_response(
+                "<p>That's not the right answer; your answer is too high.</p>"
+            ),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            parse_response(
+                "<p>That's not the right answer; your answer is too low.</p>"
+            ),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse_response("<p>That's not the right answer. If you're stuck"),
+            Outcome::Incorrect
+        );
+        assert_eq!(
+            parse_response(
+                "<p>You gave an answer too recently; you have to wait after \
+                 submitting an answer before trying again.  You have 1m 23s \
+                 left to wait.</p>"
+            ),
+            Outcome::Wait { seconds: Some(83) }
+        );
+        assert_eq!(parse_cooldown("<p>That's not the right answer."), None);
+        assert_eq!(
+            parse_response(
+                "<p>You don't seem to be solving the right level.  Did you \
+                 already complete it?</p>"
+            ),
+            Outcome::WrongLevel
+        );
+        assert_eq!(parse_response("<html></html>"), Outcome::Unknown);
+    }
+
+    #[test]
+    fn test_outcome_tags_round_trip() {
+        for outcome in [
+            Outcome::Correct,
+            Outcome::Incorrect,
+            Outcome::TooHigh,
+            Outcome::TooLow,
+            Outcome::Wait { seconds: Some(30) },
+            Outcome::Wait { seconds: None },
+            Outcome::WrongLevel,
+            Outcome::Unknown,
+        ] {
+            assert_eq!(Outcome::from_tag(&outcome.tag()), outcome);
+        }
+    }
+
+    #[test]
+    fn test_parse_solver_output() {
+        let output = "Day 10 - Part 1: 507\nDay 10 - Part 2 bfs: 33\n\
+                      Day 10 - Part 2 linalg: 34\n";
+        assert_eq!(parse_solver_output(output, 10, 1), Some("507".into()));
+        assert_eq!(parse_solver_output(output, 10, 2), Some("33".into()));
+        assert_eq!(parse_solver_output(output, 9, 1), None);
+    }
+
+    fn attempt(part: u32, answer: &str, outcome: Outcome, t: u64) -> Attempt {
+        Attempt {
+            day: 5,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            timestamp: t,
+            cooldown: None,
+        }
+    }
+
+    #[test]
+    fn test_history_guard() {
+        let history = History {
+            attempts: vec![
+                attempt(1, "100", Outcome::TooHigh, 0),
+                attempt(1, "10", Outcome::TooLow, 100),
+                attempt(1, "50", Outcome::Incorrect, 200),
+                attempt(2, "7", Outcome::Correct, 300),
+            ],
+        };
+        assert_eq!(
+            history.check(5, 1, "50", 1000),
+            Err(Refusal::KnownWrong(Outcome::Incorrect))
+        );
+        assert_eq!(
+            history.check(5, 1, "150", 1000),
+            Err(Refusal::NotBelow("100".into()))
+        );
+        assert_eq!(
+            history.check(5, 1, "3", 1000),
+            Err(Refusal::NotAbove("10".into()))
+        );
+        assert_eq!(
+            history.check(5, 2, "8", 1000),
+            Err(Refusal::AlreadySolved("7".into()))
+        );
+        assert_eq!(history.check(5, 1, "42", 1000), Ok(()));
+        assert_eq!(history.check(6, 1, "50", 1000), Ok(()));
+    }
+
+    #[test]
+    fn test_history_rate_limit() {
+        let history = History {
+            attempts: vec![attempt(
+                1,
+                "1",
+                Outcome::Wait { seconds: Some(40) },
+                1000,
+            )],
+        };
+        assert_eq!(
+            history.check(9, 1, "2", 1010),
+            Err(Refusal::RateLimited { seconds_left: 30 })
+        );
+        assert_eq!(history.check(9, 1, "2", 1040), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_cooldown() {
+        assert_eq!(
+            parse_cooldown(
+                "<p>That's not the right answer. Please wait one minute \
+                 before trying again.</p>"
+            ),
+            Some(60)
+        );
+        assert_eq!(
+            parse_cooldown(
+                "<p>Because you have guessed incorrectly 4 times on this \
+                 puzzle, please wait 5 minutes before trying again.</p>"
+            ),
+            Some(300)
+        );
+        assert_eq!(
+            parse_cooldown("please wait a bit before trying again"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_history_wrong_then_resubmit() {
+        let mut history = History {
+            attempts: vec![Attempt {
+                cooldown: Some(300),
+                ..attempt(1, "50", Outcome::TooHigh, 1000)
+            }],
+        };
+        // any other answer has to wait out the cooldown, even another day's
+        assert_eq!(
+            history.check(5, 1, "40", 1100),
+            Err(Refusal::RateLimited { seconds_left: 200 })
+        );
+        assert_eq!(
+            history.check(6, 1, "40", 1299),
+            Err(Refusal::RateLimited { seconds_left: 1 })
+        );
+        assert_eq!(history.check(5, 1, "40", 1300), Ok(()));
+
+        // without a parsed cooldown it's still at least a minute
+        history
+            .attempts
+            .push(attempt(1, "40", Outcome::Incorrect, 2000));
+        assert_eq!(
+            history.check(5, 1, "30", 2010),
+            Err(Refusal::RateLimited { seconds_left: 50 })
+        );
+        assert_eq!(history.check(5, 1, "30", 2060), Ok(()));
+    }
+
+    #[test]
+    fn test_history_file_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("aoc_history_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut history = History::load(&path).unwrap();
+        assert!(history.attempts.is_empty());
+        let wrong = Attempt {
+            cooldown: Some(60),
+            ..attempt(1, "100", Outcome::TooHigh, 5)
+        };
+        history.record(&path, wrong).unwrap();
+        history
+            .record(&path, attempt(1, "42", Outcome::Correct, 90))
+            .unwrap();
+
+        let loaded = History::load(&path).unwrap();
+        assert_eq!(loaded.attempts, history.attempts);
+
+        // lines without the cooldown still load
+        fs::write(&path, "5\t1\t100\ttoo-high\t5\n").unwrap();
+        let loaded = History::load(&path).unwrap();
+        assert_eq!(loaded.attempts[0].cooldown, None);
+        fs::remove_file(&path).unwrap();
+    }
+}